@@ -1,11 +1,13 @@
 /// Accuracy in meters
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub enum Accuracy {
+    #[default]
     One,            // 10 digit
     Ten,            // 8 digit
     OneHundred,     // 6 digit
     OneThousand,    // 4 digit
     TenThousand,    // 2 digit
+    OneHundredThousand, // 0 digit, the 100km square alone
 }
 
 impl Accuracy {
@@ -19,6 +21,7 @@ impl Accuracy {
             3 => Some(Accuracy::OneHundred),
             4 => Some(Accuracy::OneThousand),
             5 => Some(Accuracy::TenThousand),
+            6 => Some(Accuracy::OneHundredThousand),
             _ => None
         }
     }
@@ -33,6 +36,7 @@ impl Accuracy {
             100 => Some(Accuracy::OneHundred),
             1000 => Some(Accuracy::OneThousand),
             10000 => Some(Accuracy::TenThousand),
+            100000 => Some(Accuracy::OneHundredThousand),
             _ => None
         }
     }
@@ -47,6 +51,7 @@ impl Accuracy {
             6 => Some(Accuracy::OneHundred),
             4 => Some(Accuracy::OneThousand),
             2 => Some(Accuracy::TenThousand),
+            0 => Some(Accuracy::OneHundredThousand),
             _ => None
         }
     }
@@ -61,6 +66,7 @@ impl Accuracy {
             Accuracy::OneHundred => 3,
             Accuracy::OneThousand => 4,
             Accuracy::TenThousand => 5,
+            Accuracy::OneHundredThousand => 6,
         }
     }
 
@@ -74,6 +80,7 @@ impl Accuracy {
             Accuracy::OneHundred => 100,
             Accuracy::OneThousand => 1000,
             Accuracy::TenThousand => 10000,
+            Accuracy::OneHundredThousand => 100000,
         }
     }
 
@@ -87,12 +94,91 @@ impl Accuracy {
             Accuracy::OneHundred => 6,
             Accuracy::OneThousand => 4,
             Accuracy::TenThousand => 2,
+            Accuracy::OneHundredThousand => 0,
+        }
+    }
+
+    pub fn from_geohash_len(len: usize) -> Self {
+        // geohash cells of about the size of the MGRS cell, at the equator
+        // <=3 - OneHundredThousand (156km)
+        // 4, 5 - TenThousand (39km x 20km, 4.9km)
+        // 6 - OneThousand (1.2km x 610m)
+        // 7 - OneHundred (153m)
+        // 8, 9 - Ten (38m x 19m, 4.8m)
+        // >=10 - One (1.2m x 60cm)
+        match len {
+            0..=3 => Accuracy::OneHundredThousand,
+            4 | 5 => Accuracy::TenThousand,
+            6 => Accuracy::OneThousand,
+            7 => Accuracy::OneHundred,
+            8 | 9 => Accuracy::Ten,
+            _ => Accuracy::One,
+        }
+    }
+
+    pub fn as_geohash_len(&self) -> usize {
+        // 10 - One
+        // 8 - Ten
+        // 7 - OneHundred, etc.
+        match *self {
+            Accuracy::One => 10,
+            Accuracy::Ten => 8,
+            Accuracy::OneHundred => 7,
+            Accuracy::OneThousand => 6,
+            Accuracy::TenThousand => 5,
+            Accuracy::OneHundredThousand => 3,
+        }
+    }
+
+    pub fn from_plus_code_len(len: usize) -> Self {
+        // plus code cells of about the size of the MGRS cell, at the equator
+        // <=4 - OneHundredThousand (110km)
+        // 5, 6 - TenThousand (5.5km)
+        // 8 - OneHundred (275m)
+        // 10 - Ten (14m)
+        // >=11 - One (3.5m x 2.8m)
+        match len {
+            0..=4 => Accuracy::OneHundredThousand,
+            5 | 6 => Accuracy::TenThousand,
+            7 | 8 => Accuracy::OneHundred,
+            9 | 10 => Accuracy::Ten,
+            _ => Accuracy::One,
         }
     }
 }
 
-impl Default for Accuracy {
-    fn default() -> Self {
-        Accuracy::One
+/// How a grid reference is brought to a different `Accuracy`, see `Mgrs::with_accuracy`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RoundingMode {
+    /// Round down to the south-west corner of the cell containing the point, as MGRS requires
+    Truncate,
+    /// Round to the nearest cell corner
+    Nearest,
+    /// Move to the cell at the center of the current cell, or to the cell containing it when
+    /// moving to a coarser accuracy
+    Center,
+}
+
+#[cfg(test)]
+mod test {
+    use super::Accuracy;
+
+    #[test]
+    fn geohash_len() {
+        assert_eq!(Accuracy::from_geohash_len(1), Accuracy::OneHundredThousand);
+        assert_eq!(Accuracy::from_geohash_len(3), Accuracy::OneHundredThousand);
+        assert_eq!(Accuracy::from_geohash_len(4), Accuracy::TenThousand);
+        assert_eq!(Accuracy::from_geohash_len(5), Accuracy::TenThousand);
+        assert_eq!(Accuracy::from_geohash_len(6), Accuracy::OneThousand);
+        assert_eq!(Accuracy::from_geohash_len(7), Accuracy::OneHundred);
+        assert_eq!(Accuracy::from_geohash_len(8), Accuracy::Ten);
+        assert_eq!(Accuracy::from_geohash_len(9), Accuracy::Ten);
+        assert_eq!(Accuracy::from_geohash_len(10), Accuracy::One);
+        assert_eq!(Accuracy::from_geohash_len(12), Accuracy::One);
+
+        for a in &[Accuracy::One, Accuracy::Ten, Accuracy::OneHundred, Accuracy::OneThousand, Accuracy::TenThousand,
+                  Accuracy::OneHundredThousand] {
+            assert_eq!(Accuracy::from_geohash_len(a.as_geohash_len()), *a);
+        }
     }
 }
@@ -1,13 +1,13 @@
 use std::convert::From;
 use std::str::{self, FromStr};
-use std::error::Error;
 use std::fmt;
 
 use Errors;
 
-#[derive(PartialEq, PartialOrd, Debug, Copy, Clone)]
+#[derive(PartialEq, PartialOrd, Debug, Copy, Clone, Default)]
 /// 8° latitudinal band (C..X covering 80°S..84°N) **note:** X is repeated for 80-84°N
 pub enum LatBand {
+    #[default]
     C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X
 }
 
@@ -25,26 +25,26 @@ impl LatBand {
 
         use self::LatBand::{C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X};
         match l as isize {
-            l if ((84 >= l) && (l >= 72)) => Some(X),
-            l if ((72 > l)  && (l >= 64)) => Some(W),
-            l if ((64 > l)  && (l >= 56)) => Some(V),
-            l if ((56 > l)  && (l >= 48)) => Some(U),
-            l if ((48 > l)  && (l >= 40)) => Some(T),
-            l if ((40 > l)  && (l >= 32)) => Some(S),
-            l if ((32 > l)  && (l >= 24)) => Some(R),
-            l if ((24 > l)  && (l >= 16)) => Some(Q),
-            l if ((16 > l)  && (l >= 8))  => Some(P),
-            l if ((8 > l)   && (l >= 0))  => Some(N),
-            l if ((0 > l)   && (l >= -8)) => Some(M),
-            l if ((-8 > l)  && (l >= -16)) => Some(L),
-            l if ((-16 > l) && (l >= -24)) => Some(K),
-            l if ((-24 > l) && (l >= -32)) => Some(J),
-            l if ((-32 > l) && (l >= -40)) => Some(H),
-            l if ((-40 > l) && (l >= -48)) => Some(G),
-            l if ((-48 > l) && (l >= -56)) => Some(F),
-            l if ((-56 > l) && (l >= -64)) => Some(E),
-            l if ((-64 > l) && (l >= -72)) => Some(D),
-            l if ((-72 > l) && (l >= -80)) => Some(C),
+            l if (72..=84).contains(&l) => Some(X),
+            l if (64..72).contains(&l) => Some(W),
+            l if (56..64).contains(&l) => Some(V),
+            l if (48..56).contains(&l) => Some(U),
+            l if (40..48).contains(&l) => Some(T),
+            l if (32..40).contains(&l) => Some(S),
+            l if (24..32).contains(&l) => Some(R),
+            l if (16..24).contains(&l) => Some(Q),
+            l if (8..16).contains(&l)  => Some(P),
+            l if (0..8).contains(&l)  => Some(N),
+            l if (-8..0).contains(&l) => Some(M),
+            l if (-16..-8).contains(&l) => Some(L),
+            l if (-24..-16).contains(&l) => Some(K),
+            l if (-32..-24).contains(&l) => Some(J),
+            l if (-40..-32).contains(&l) => Some(H),
+            l if (-48..-40).contains(&l) => Some(G),
+            l if (-56..-48).contains(&l) => Some(F),
+            l if (-64..-56).contains(&l) => Some(E),
+            l if (-72..-64).contains(&l) => Some(D),
+            l if (-80..-72).contains(&l) => Some(C),
             _ => None,
         }
 
@@ -101,6 +101,7 @@ impl LatBand {
     }
 
     pub fn index(&self) -> usize {
+        use self::LatBand::{C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X};
         match *self {
             C => 0, D => 1, E => 2, F => 3, G => 4, H => 5, J => 6, K => 7, L => 8, M => 9,
             N => 10, P => 11, Q => 12, R => 13, S => 14, T => 15, U => 16, V => 17, W => 18,
@@ -108,6 +109,11 @@ impl LatBand {
         }
     }
 
+    pub fn from_index(i: usize) -> Option<Self> {
+        use self::LatBand::{C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X};
+        [C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X].get(i).cloned()
+    }
+
     pub fn as_char(&self) -> char {
         use self::LatBand::{C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X};
         match *self {
@@ -139,7 +145,7 @@ impl From<f64> for LatBand {
         this is not the desired behavior, prefer the `ZoneLetter::letter_for_lat` instead.
         */
 
-        return match LatBand::from_lat(lat) {
+        match LatBand::from_lat(lat) {
             Some(z) => z,
             None => panic!("No Grid Zone Letter for Lattitude: {}", lat),
         }
@@ -173,7 +179,7 @@ impl From<u8> for LatBand {
 
 impl<'s> From<&'s str> for LatBand {
     fn from(s: &'s str) -> Self {
-        LatBand::from(s)
+        s.parse().unwrap_or_else(|_| panic!("invalid latitude band letter {}", s))
     }
 }
 
@@ -211,16 +217,9 @@ impl FromStr for LatBand {
     }
 }
 
-impl Default for LatBand {
-    fn default() -> Self {
-        // Shuold we use an "invalid" letter?
-        LatBand::C
-    }
-}
-
 impl fmt::Display for LatBand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let c: char = (*self).into();
-        writeln!(f, "{}", c)
+        write!(f, "{}", c)
     }
 }
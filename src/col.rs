@@ -1,23 +1,20 @@
 use std::fmt;
 
-use ascii;
 use Errors;
 
-use SET_ORIGIN_COLUMN_LETTERS;
-
-#[derive(PartialEq, PartialOrd, Debug, Copy, Clone)]
+#[derive(PartialEq, PartialOrd, Debug, Copy, Clone, Default)]
 /// 100km grid square column letters
 ///
 /// Repeats every third zone with sets: 'ABCDEFGH', 'JKLMNPQR', 'STUVWXYZ'
 pub enum ColLetter {
+    #[default]
     A, B, C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z
 }
 
-
 impl ColLetter {
     pub fn as_meters_from_zone(&self, zone: u8) -> usize {
-        // get easting specified by e100k
-        self.index_from_set((zone-1)%3) * 100000
+        // get easting specified by e100k (column letters start at 100km)
+        (self.index_from_set((zone-1)%3) + 1) * 100000
     }
 
     pub fn index_for_easting(easting: i32) -> u8 {
@@ -115,13 +112,14 @@ impl From<u32> for ColLetter {
 
 impl From<char> for ColLetter {
     fn from(c: char) -> Self {
-        ColLetter::from(c as u32)
+        c.to_string().parse().expect("invalid 100km grid square column letter")
     }
 }
 
-impl Default for ColLetter {
-    fn default() -> Self {
-        ColLetter::A
+impl fmt::Display for ColLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c: char = (*self).into();
+        write!(f, "{}", c)
     }
 }
 
@@ -154,12 +152,14 @@ mod test {
     #[test]
     fn from_str() {
         let c = "c";
-        let cl: ColLetter = c.parse();
-        assert_eq!(cl, Ok(ColLetter::C));
+        let cl: ColLetter = c.parse().unwrap();
+        assert_eq!(cl, ColLetter::C);
 
         let c = "C";
-        let cl: ColLetter = c.parse();
-        assert_eq!(cl, Ok(ColLetter::C));
+        let cl: ColLetter = c.parse().unwrap();
+        assert_eq!(cl, ColLetter::C);
+
+        assert!("I".parse::<ColLetter>().is_err());
     }
 
     #[test]
@@ -169,10 +169,3 @@ mod test {
         assert_eq!(c, 'C');
     }
 }
-
-impl fmt::Display for ColLetter {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let c: char = (*self).into();
-        writeln!(f, "{}", c)
-    }
-}
@@ -0,0 +1,226 @@
+use Accuracy;
+use gzd::{Gzd, GridSquareId100k};
+use mgrs::Mgrs;
+use latlon::LatLon;
+use datum::Datum;
+use col::ColLetter;
+use row::RowLetter;
+use band::LatBand;
+use hemisphere::Hemisphere;
+use tm::TransverseMercator;
+use utm::Utm;
+use polygon;
+
+// spacing of the points the outline of an area is sampled at when finding its extent on the grid
+const SAMPLE_DEGREES: f64 = 0.01;
+
+/// Iterator over the MGRS cells covering an area, see `Mgrs::covering`
+///
+/// Cells are generated as they are iterated, one GZD at a time, so large areas are never held in
+/// memory.
+#[derive(Debug)]
+pub struct MgrsCells {
+    area: Vec<LatLon>,
+    accuracy: Accuracy,
+    // GZDs overlapping the bounds of the area, still to visit
+    gzds: ::std::vec::IntoIter<Gzd>,
+    zone: Option<ZoneCells>,
+}
+
+// cells of a single GZD, visited row by row from the south-west
+#[derive(Debug)]
+struct ZoneCells {
+    gzd: Gzd,
+    tm: TransverseMercator,
+    // the area clipped to the GZD
+    area: Vec<LatLon>,
+    min_easting: i32,
+    max_easting: i32,
+    max_northing: i32,
+    easting: i32,
+    northing: i32,
+}
+
+impl MgrsCells {
+    pub fn new(area: &[LatLon], accuracy: Accuracy) -> Self {
+        /*!
+        Creates an iterator over the cells overlapping an area.
+
+        ### Params
+         * **area**: Outline of the area; it must not cross the antimeridian.
+         * **accuracy**: Size of the cells to return.
+        */
+
+        let area: Vec<LatLon> = area.iter().map(|ll| ll.to_datum(Datum::Wgs84)).collect();
+        let bounds = polygon::bounds(&area);
+        let gzds: Vec<Gzd> = Gzd::all()
+            .filter(|gzd| {
+                let rect = gzd.bounds().expect("all GZDs are valid");
+                rect[0].lat < bounds[1].lat && bounds[0].lat < rect[1].lat &&
+                rect[0].lon < bounds[1].lon && bounds[0].lon < rect[1].lon
+            })
+            .collect();
+        MgrsCells {
+            area,
+            accuracy,
+            gzds: gzds.into_iter(),
+            zone: None,
+        }
+    }
+
+    fn zone_cells(&self, gzd: Gzd) -> Option<ZoneCells> {
+        /*!
+        Finds the cells of a GZD which may overlap the area.
+
+        ### Return
+         * **Some** The extent of the area on the GZD's grid, snapped to the cells.
+         * **None** If the GZD does not overlap the area.
+        */
+
+        let area = polygon::clip_to_rect(&self.area, &gzd.bounds().ok()?);
+        if polygon::area(&area) <= 0.0 { return None; }
+
+        let tm = zone_projection(&gzd);
+
+        let (min_e, min_n, max_e, max_n) = grid_extent(&tm, &area);
+
+        // allow a cell either side for the distance between samples
+        let size = self.accuracy.as_distance() as f64;
+        let min_easting = (f64::floor(min_e / size) * size - size) as i32;
+        let min_northing = (f64::floor(min_n / size) * size - size) as i32;
+        Some(ZoneCells {
+            gzd,
+            tm,
+            area,
+            min_easting,
+            max_easting: (f64::floor(max_e / size) * size + size) as i32,
+            max_northing: (f64::floor(max_n / size) * size + size) as i32,
+            easting: min_easting,
+            northing: min_northing,
+        })
+    }
+}
+
+impl ZoneCells {
+    fn overlaps(&self, size: i32) -> bool {
+        /*!
+        Returns whether the current cell shares any area with the area clipped to this GZD.
+        */
+
+        let steps = 4;
+        let mut edge = Vec::with_capacity(4 * steps as usize);
+        for i in 0..steps { edge.push((size * i / steps, 0)); }
+        for i in 0..steps { edge.push((size, size * i / steps)); }
+        for i in 0..steps { edge.push((size - size * i / steps, size)); }
+        for i in 0..steps { edge.push((0, size - size * i / steps)); }
+
+        let outline: Vec<LatLon> = edge.iter()
+            .map(|&(de, dn)| self.tm.inverse((self.easting + de) as f64, (self.northing + dn) as f64))
+            .collect();
+        polygon::area(&polygon::clip_to_convex(&self.area, &outline)) > 0.0
+    }
+}
+
+impl Iterator for MgrsCells {
+    type Item = Mgrs;
+
+    fn next(&mut self) -> Option<Mgrs> {
+        let size = self.accuracy.as_distance() as i32;
+        loop {
+            if let Some(ref mut cells) = self.zone {
+                while cells.northing <= cells.max_northing {
+                    let (easting, northing) = (cells.easting, cells.northing);
+                    let overlaps = cells.overlaps(size);
+                    cells.easting += size;
+                    if cells.easting > cells.max_easting {
+                        cells.easting = cells.min_easting;
+                        cells.northing += size;
+                    }
+
+                    if overlaps {
+                        let zone = cells.gzd.zone;
+                        return Some(Mgrs {
+                            gzd: cells.gzd,
+                            gsid_100k: GridSquareId100k {
+                                col: ColLetter::from_zone_and_easting(zone, easting),
+                                row: RowLetter::from_zone_and_northing(zone, northing),
+                            },
+                            easting: (easting % 100000) as usize,
+                            northing: (northing % 100000) as usize,
+                            accuracy: self.accuracy,
+                        });
+                    }
+                }
+            }
+
+            let gzd = self.gzds.next()?;
+            self.zone = self.zone_cells(gzd);
+        }
+    }
+}
+
+pub fn zone_projection(gzd: &Gzd) -> TransverseMercator {
+    /*!
+    Returns the projection of the UTM zone and hemisphere a GZD lies within.
+    */
+
+    let hemisphere = if gzd.band < LatBand::N { Hemisphere::S } else { Hemisphere::N };
+    Utm { zone: gzd.zone, hemisphere, ..Default::default() }.projection()
+}
+
+pub fn grid_extent(tm: &TransverseMercator, area: &[LatLon]) -> (f64, f64, f64, f64) {
+    /*!
+    Returns the extent of an area on a grid.
+
+    Grid lines curve across latitude/longitude, so the edges of the area are sampled densely.
+
+    ### Return
+     * The minimum easting and northing, then the maximum easting and northing.
+    */
+
+    let (mut min_e, mut min_n, mut max_e, mut max_n) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for i in 0..area.len() {
+        let (a, b) = (&area[i], &area[(i + 1) % area.len()]);
+        let steps = f64::ceil(f64::max((b.lat - a.lat).abs(), (b.lon - a.lon).abs()) / SAMPLE_DEGREES).max(1.0);
+        for s in 0..steps as usize {
+            let t = s as f64 / steps;
+            let p = tm.forward(&LatLon { lat: a.lat + t * (b.lat - a.lat), lon: a.lon + t * (b.lon - a.lon), ..*a });
+            min_e = min_e.min(p.easting);
+            max_e = max_e.max(p.easting);
+            min_n = min_n.min(p.northing);
+            max_n = max_n.max(p.northing);
+        }
+    }
+    (min_e, min_n, max_e, max_n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::MgrsCells;
+    use Accuracy;
+    use mgrs::Mgrs;
+    use latlon::LatLon;
+
+    #[test]
+    fn across_gzd_corner() {
+        // the corner of 31T, 32T, 31U and 32U
+        let rect = [LatLon { lat: 47.95, lon: 5.95, ..Default::default() },
+                    LatLon { lat: 48.05, lon: 6.05, ..Default::default() }];
+        let cells: Vec<String> = Mgrs::covering_rect(&rect, Accuracy::TenThousand).map(|m| m.to_string()).collect();
+        assert_eq!(cells, vec!["31T GP 2 1", "31T GP 2 2", "32T KU 7 1", "32T KU 7 2",
+                               "31U GP 1 2", "31U GP 2 2", "32U KU 7 2", "32U KU 8 2"]);
+    }
+
+    #[test]
+    fn triangle() {
+        let area = [LatLon::new(48.85, 2.28).unwrap(), LatLon::new(48.85, 2.30).unwrap(),
+                    LatLon::new(48.87, 2.29).unwrap()];
+        let cells: Vec<Mgrs> = MgrsCells::new(&area, Accuracy::OneThousand).collect();
+        assert!(!cells.is_empty());
+        assert!(cells.iter().all(|m| m.gzd.to_string() == "31U" && m.accuracy == Accuracy::OneThousand));
+        // every vertex lies in one of the cells
+        for ll in &area {
+            assert!(cells.iter().any(|m| m.contains_latlon(ll)));
+        }
+    }
+}
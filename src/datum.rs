@@ -1,41 +1,192 @@
 use std::str::FromStr;
-use std::ascii::AsciiExt;
 
 use Errors;
-// major (equatorial) radius in meters
-const WGS84_ELLIPSOID_A: f64 = 6378137.0;
-// polar semi-minor axis in meters
-const WGS84_ELLIPSOID_B: f64 = 6356752.314245;
-// flattening
-const WGS84_ELLIPSOID_F: f64 = 1.0 / 298.257223563;
-
-#[derive(Copy, Clone, Debug)]
+use ellipsoid::Ellipsoid;
+use row::Lettering;
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub enum Datum {
-    Wgs84
+    #[default]
+    Wgs84,
+    /// Pulkovo 1942 (SK-42), used across the former Soviet Union and eastern Europe
+    Pulkovo1942,
+    /// Ordnance Survey of Great Britain 1936, used by the British National Grid
+    Osgb36,
+    /// Ireland 1975, used by the Irish Grid
+    Tm75,
+    /// IRENET95, the Irish realisation of ETRS89 used by Irish Transverse Mercator
+    Irenet95,
+    /// North American Datum 1983, used by the US National Grid
+    Nad83,
+    /// CH1903, used by the Swiss LV03 grid
+    Ch1903,
+    /// CH1903+, used by the Swiss LV95 grid
+    Ch1903Plus,
+    /// North American Datum 1927, used by older US and Canadian maps
+    Nad27,
+    /// Arc 1960, used by older maps of east Africa
+    Arc1960,
+    /// Tokyo, used by older maps of Japan and Korea
+    Tokyo,
+    /// Deutsches Hauptdreiecksnetz, used by the German Gauss-Krüger grid
+    Dhdn,
 }
 
 impl Datum {
-    fn a(&self) -> f64 {
+    pub fn ellipsoid(&self) -> Ellipsoid {
         match *self {
-            Datum::Wgs84 => WGS84_ELLIPSOID_A
+            Datum::Wgs84 => Ellipsoid::Wgs84,
+            Datum::Pulkovo1942 => Ellipsoid::Krassowsky1940,
+            Datum::Osgb36 => Ellipsoid::Airy1830,
+            Datum::Tm75 => Ellipsoid::AiryModified,
+            Datum::Irenet95 => Ellipsoid::Grs80,
+            Datum::Nad83 => Ellipsoid::Grs80,
+            Datum::Ch1903 => Ellipsoid::Bessel1841,
+            Datum::Ch1903Plus => Ellipsoid::Bessel1841,
+            Datum::Nad27 => Ellipsoid::Clarke1866,
+            Datum::Arc1960 => Ellipsoid::Clarke1880,
+            Datum::Tokyo => Ellipsoid::Bessel1841,
+            Datum::Dhdn => Ellipsoid::Bessel1841,
         }
     }
-    fn b(&self) -> f64 {
+    pub fn a(&self) -> f64 {
+        self.ellipsoid().a()
+    }
+    pub fn b(&self) -> f64 {
+        self.ellipsoid().b()
+    }
+    pub fn f(&self) -> f64 {
+        self.ellipsoid().f()
+    }
+
+    pub fn transform(&self) -> [f64; 7] {
+        /*!
+        Helmert transform parameters from WGS84 to this datum.
+
+        ### Returns
+         * `[tx, ty, tz, s, rx, ry, rz]`: translations in metres, scale in ppm and rotations in
+           arcseconds (position vector convention).
+        */
+
         match *self {
-            Datum::Wgs84 => WGS84_ELLIPSOID_B
+            Datum::Wgs84 => [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            // GOST R 51794-2008 (EPSG:5044), published as a coordinate frame rotation
+            Datum::Pulkovo1942 => [-23.57, 140.95, 79.8, 0.22, 0.0, -0.35, -0.79],
+            // Ordnance Survey 'A guide to coordinate systems in Great Britain'
+            Datum::Osgb36 => [-446.448, 125.157, -542.060, 20.4894, -0.1502, -0.2470, -0.8421],
+            // Ordnance Survey Ireland
+            Datum::Tm75 => [-482.530, 130.596, -564.557, -8.150, 1.042, 0.214, 0.631],
+            // ETRS89 realisation, coincident with WGS84 at the metre level
+            Datum::Irenet95 => [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            // treated as equivalent to WGS84, as the US National Grid does
+            Datum::Nad83 => [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            // swisstopo, the same translations serving both Swiss frames at the metre level
+            Datum::Ch1903 => [-674.374, -15.056, -405.346, 0.0, 0.0, 0.0, 0.0],
+            Datum::Ch1903Plus => [-674.374, -15.056, -405.346, 0.0, 0.0, 0.0, 0.0],
+            // NIMA TR8350.2, mean solutions for the continental US, Kenya and Tanzania, and Japan
+            Datum::Nad27 => [8.0, -160.0, -176.0, 0.0, 0.0, 0.0, 0.0],
+            Datum::Arc1960 => [160.0, 6.0, 302.0, 0.0, 0.0, 0.0, 0.0],
+            Datum::Tokyo => [148.0, -507.0, -685.0, 0.0, 0.0, 0.0, 0.0],
+            // EPSG:1777, the mean solution for the former West Germany
+            Datum::Dhdn => [-598.1, -73.7, -418.2, -6.7, -0.202, -0.045, 2.455],
         }
     }
-    fn f(&self) -> f64 {
+
+    pub fn lettering(&self) -> Lettering {
+        /*!
+        Returns the 100km square lettering MGRS grid references on this datum use.
+
+        Military maps on the legacy NAD27, Arc 1960 and Tokyo datums were lettered with the older AL
+        scheme, all others with the AA scheme. Sharing an ellipsoid is not enough: the Swiss and
+        German datums are on Bessel 1841 like Tokyo, but have no AL lettered MGRS maps. See
+        `Lettering`.
+        */
+
         match *self {
-            Datum::Wgs84 => WGS84_ELLIPSOID_F
+            Datum::Nad27 | Datum::Arc1960 | Datum::Tokyo => Lettering::AL,
+            _ => Lettering::AA,
         }
     }
 }
 
-impl Default for Datum {
-    fn default() -> Self {
-        Datum::Wgs84
-    }
+pub fn to_cartesian(lat: f64, lon: f64, ellipsoid: Ellipsoid) -> [f64; 3] {
+    /*!
+    Converts latitude/longitude on `ellipsoid` to geocentric (ECEF) cartesian coordinates, assuming
+    a height of 0 on the ellipsoid.
+
+    ### Returns
+     * `[x, y, z]` in metres.
+    */
+
+    let phi = lat.to_radians();
+    let lamda = lon.to_radians();
+    let a = ellipsoid.a();
+    let f = ellipsoid.f();
+
+    let e_sq = 2.0 * f - f * f; // 1st eccentricity squared ≡ (a²-b²)/a²
+    let nu = a / f64::sqrt(1.0 - e_sq * phi.sin() * phi.sin()); // radius of curvature in prime vertical
+
+    [nu * phi.cos() * lamda.cos(),
+     nu * phi.cos() * lamda.sin(),
+     nu * (1.0 - e_sq) * phi.sin()]
+}
+
+pub fn from_cartesian(p: [f64; 3], ellipsoid: Ellipsoid) -> (f64, f64) {
+    /*!
+    Converts geocentric (ECEF) cartesian coordinates to latitude/longitude on `ellipsoid`, using
+    Bowring’s (1985) formulation for μm precision in concise form.
+
+    ### Returns
+     * `(lat, lon)` in degrees.
+    */
+
+    let (x, y, z) = (p[0], p[1], p[2]);
+    let a = ellipsoid.a();
+    let b = ellipsoid.b();
+    let f = ellipsoid.f();
+
+    let e2 = 2.0 * f - f * f;   // 1st eccentricity squared ≡ (a²-b²)/a²
+    let eps2 = e2 / (1.0 - e2); // 2nd eccentricity squared ≡ (a²-b²)/b²
+    let pp = f64::sqrt(x * x + y * y); // distance from minor axis
+    let r = f64::sqrt(pp * pp + z * z); // polar radius
+
+    // parametric latitude (Bowring eqn 17, replacing tanβ = z·a / p·b)
+    let tan_beta = (b * z) / (a * pp) * (1.0 + eps2 * b / r);
+    let sin_beta = tan_beta / f64::sqrt(1.0 + tan_beta * tan_beta);
+    let cos_beta = sin_beta / tan_beta;
+
+    // geodetic latitude (Bowring eqn 18: tanφ = z+ε²bsin³β / p−e²cos³β)
+    let phi = if cos_beta.is_nan() { 0.0 } else {
+        f64::atan2(z + eps2 * b * sin_beta * sin_beta * sin_beta,
+                   pp - e2 * a * cos_beta * cos_beta * cos_beta)
+    };
+
+    // longitude
+    let lamda = f64::atan2(y, x);
+
+    (phi.to_degrees(), lamda.to_degrees())
+}
+
+pub fn apply_transform(p: [f64; 3], t: [f64; 7]) -> [f64; 3] {
+    /*!
+    Applies a Helmert transform (as returned by `Datum::transform`) to geocentric cartesian
+    coordinates.
+    */
+
+    let (x1, y1, z1) = (p[0], p[1], p[2]);
+
+    // normalise arcseconds to radians, ppm to a scale factor
+    let tx = t[0];
+    let ty = t[1];
+    let tz = t[2];
+    let s1 = t[3] / 1e6 + 1.0;
+    let rx = (t[4] / 3600.0).to_radians();
+    let ry = (t[5] / 3600.0).to_radians();
+    let rz = (t[6] / 3600.0).to_radians();
+
+    [tx + x1 * s1 - y1 * rz + z1 * ry,
+     ty + x1 * rz + y1 * s1 - z1 * rx,
+     tz - x1 * ry + y1 * rx + z1 * s1]
 }
 
 ///////////////////////////////////
@@ -44,7 +195,7 @@ impl Default for Datum {
 
 impl<S: AsRef<str>> From<S> for Datum {
     fn from(s: S) -> Self {
-        Datum::from(s.as_ref())
+        s.as_ref().parse().unwrap_or_else(|_| panic!("invalid map datum {}", s.as_ref()))
     }
 }
 
@@ -54,7 +205,68 @@ impl FromStr for Datum {
         let d = s.to_ascii_uppercase();
         match &*d {
             "WGS84" => Ok(Datum::Wgs84),
+            "PULKOVO1942" | "SK42" => Ok(Datum::Pulkovo1942),
+            "OSGB36" => Ok(Datum::Osgb36),
+            "TM75" => Ok(Datum::Tm75),
+            "IRENET95" => Ok(Datum::Irenet95),
+            "NAD83" => Ok(Datum::Nad83),
+            "CH1903" => Ok(Datum::Ch1903),
+            "CH1903+" | "CH1903PLUS" => Ok(Datum::Ch1903Plus),
+            "NAD27" => Ok(Datum::Nad27),
+            "ARC1960" => Ok(Datum::Arc1960),
+            "TOKYO" => Ok(Datum::Tokyo),
+            "DHDN" => Ok(Datum::Dhdn),
             _ => Err(Errors::InvalidDatum(s.to_owned()))
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Datum, to_cartesian, from_cartesian};
+    use ellipsoid::Ellipsoid;
+    use row::Lettering;
+    use latlon::LatLon;
+
+    #[test]
+    fn pulkovo_rotation_convention() {
+        // EPSG:5044 publishes Pulkovo 1942 to WGS84 as a coordinate frame rotation:
+        // tx 23.57, ty -140.95, tz -79.8 m, rx 0, ry -0.35, rz -0.79", ds -0.22 ppm
+        let (lat, lon) = (55.7539, 37.6208);
+        let p = to_cartesian(lat, lon, Ellipsoid::Krassowsky1940);
+        let rad = |arcsec: f64| (arcsec / 3600.0).to_radians();
+        let (rx, ry, rz, s) = (rad(0.0), rad(-0.35), rad(-0.79), 1.0 - 0.22e-6);
+        let q = [23.57 + s * (p[0] + rz * p[1] - ry * p[2]),
+                 -140.95 + s * (-rz * p[0] + p[1] + rx * p[2]),
+                 -79.8 + s * (ry * p[0] - rx * p[1] + p[2])];
+        let (wgs_lat, wgs_lon) = from_cartesian(q, Ellipsoid::Wgs84);
+
+        let ll = LatLon { lat, lon, datum: Datum::Pulkovo1942, ..Default::default() }.to_datum(Datum::Wgs84);
+        // to within a centimetre
+        assert!((ll.lat - wgs_lat).abs() < 1e-7);
+        assert!((ll.lon - wgs_lon).abs() < 1e-7);
+
+        let back = ll.to_datum(Datum::Pulkovo1942);
+        assert!((back.lat - lat).abs() < 1e-7);
+        assert!((back.lon - lon).abs() < 1e-7);
+    }
+
+    #[test]
+    fn lettering() {
+        assert_eq!(Datum::Wgs84.lettering(), Lettering::AA);
+        assert_eq!(Datum::Nad27.lettering(), Lettering::AL);
+        assert_eq!(Datum::Arc1960.lettering(), Lettering::AL);
+        assert_eq!(Datum::Tokyo.lettering(), Lettering::AL);
+        // Bessel 1841, as Tokyo, yet lettered AA
+        assert_eq!(Datum::Ch1903.lettering(), Lettering::AA);
+        assert_eq!(Datum::Ch1903Plus.lettering(), Lettering::AA);
+        assert_eq!(Datum::Dhdn.lettering(), Lettering::AA);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Datum::from("nad27"), Datum::Nad27);
+        assert_eq!(Datum::from(String::from("CH1903+")), Datum::Ch1903Plus);
+        assert!("ED50".parse::<Datum>().is_err());
+    }
+}
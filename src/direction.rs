@@ -0,0 +1,34 @@
+/// Compass direction to a neighbouring grid cell
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// All directions, clockwise from north
+    pub fn all() -> [Direction; 8] {
+        use self::Direction::{N, NE, E, SE, S, SW, W, NW};
+        [N, NE, E, SE, S, SW, W, NW]
+    }
+
+    /// Steps east and north (each -1, 0 or 1) to the neighbouring cell
+    pub fn as_offset(&self) -> (i32, i32) {
+        match *self {
+            Direction::N => (0, 1),
+            Direction::NE => (1, 1),
+            Direction::E => (1, 0),
+            Direction::SE => (1, -1),
+            Direction::S => (0, -1),
+            Direction::SW => (-1, -1),
+            Direction::W => (-1, 0),
+            Direction::NW => (-1, 1),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// WGS84 major (equatorial) radius in meters
+const WGS84_ELLIPSOID_A: f64 = 6378137.0;
+// WGS84 polar semi-minor axis in meters
+const WGS84_ELLIPSOID_B: f64 = 6356752.314245;
+// WGS84 flattening
+const WGS84_ELLIPSOID_F: f64 = 1.0 / 298.257223563;
+
+// GRS80 major (equatorial) radius in meters
+const GRS80_ELLIPSOID_A: f64 = 6378137.0;
+// GRS80 polar semi-minor axis in meters
+const GRS80_ELLIPSOID_B: f64 = 6356752.314140;
+// GRS80 flattening
+const GRS80_ELLIPSOID_F: f64 = 1.0 / 298.257222101;
+
+// Krassowsky 1940 major (equatorial) radius in meters
+const KRASSOWSKY_ELLIPSOID_A: f64 = 6378245.0;
+// Krassowsky 1940 polar semi-minor axis in meters
+const KRASSOWSKY_ELLIPSOID_B: f64 = 6356863.019;
+// Krassowsky 1940 flattening
+const KRASSOWSKY_ELLIPSOID_F: f64 = 1.0 / 298.3;
+
+// Airy 1830 major (equatorial) radius in meters
+const AIRY1830_ELLIPSOID_A: f64 = 6377563.396;
+// Airy 1830 polar semi-minor axis in meters
+const AIRY1830_ELLIPSOID_B: f64 = 6356256.909;
+// Airy 1830 flattening
+const AIRY1830_ELLIPSOID_F: f64 = 1.0 / 299.3249646;
+
+// Airy Modified 1849 major (equatorial) radius in meters
+const AIRY_MODIFIED_ELLIPSOID_A: f64 = 6377340.189;
+// Airy Modified 1849 polar semi-minor axis in meters
+const AIRY_MODIFIED_ELLIPSOID_B: f64 = 6356034.448;
+// Airy Modified 1849 flattening
+const AIRY_MODIFIED_ELLIPSOID_F: f64 = 1.0 / 299.3249646;
+
+// Bessel 1841 major (equatorial) radius in meters
+const BESSEL1841_ELLIPSOID_A: f64 = 6377397.155;
+// Bessel 1841 polar semi-minor axis in meters
+const BESSEL1841_ELLIPSOID_B: f64 = 6356078.963;
+// Bessel 1841 flattening
+const BESSEL1841_ELLIPSOID_F: f64 = 1.0 / 299.1528128;
+
+// Clarke 1866 major (equatorial) radius in meters
+const CLARKE1866_ELLIPSOID_A: f64 = 6378206.4;
+// Clarke 1866 polar semi-minor axis in meters
+const CLARKE1866_ELLIPSOID_B: f64 = 6356583.8;
+// Clarke 1866 flattening
+const CLARKE1866_ELLIPSOID_F: f64 = 1.0 / 294.9786982;
+
+// Clarke 1880 (RGS) major (equatorial) radius in meters
+const CLARKE1880_ELLIPSOID_A: f64 = 6378249.145;
+// Clarke 1880 (RGS) polar semi-minor axis in meters
+const CLARKE1880_ELLIPSOID_B: f64 = 6356514.870;
+// Clarke 1880 (RGS) flattening
+const CLARKE1880_ELLIPSOID_F: f64 = 1.0 / 293.465;
+
+/// Reference ellipsoid a datum or projection is defined on
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum Ellipsoid {
+    #[default]
+    Wgs84,
+    Grs80,
+    Krassowsky1940,
+    Airy1830,
+    AiryModified,
+    Bessel1841,
+    Clarke1866,
+    Clarke1880,
+}
+
+impl Ellipsoid {
+    /// Major (equatorial) radius in meters
+    pub fn a(&self) -> f64 {
+        match *self {
+            Ellipsoid::Wgs84 => WGS84_ELLIPSOID_A,
+            Ellipsoid::Grs80 => GRS80_ELLIPSOID_A,
+            Ellipsoid::Krassowsky1940 => KRASSOWSKY_ELLIPSOID_A,
+            Ellipsoid::Airy1830 => AIRY1830_ELLIPSOID_A,
+            Ellipsoid::AiryModified => AIRY_MODIFIED_ELLIPSOID_A,
+            Ellipsoid::Bessel1841 => BESSEL1841_ELLIPSOID_A,
+            Ellipsoid::Clarke1866 => CLARKE1866_ELLIPSOID_A,
+            Ellipsoid::Clarke1880 => CLARKE1880_ELLIPSOID_A,
+        }
+    }
+
+    /// Polar semi-minor axis in meters
+    pub fn b(&self) -> f64 {
+        match *self {
+            Ellipsoid::Wgs84 => WGS84_ELLIPSOID_B,
+            Ellipsoid::Grs80 => GRS80_ELLIPSOID_B,
+            Ellipsoid::Krassowsky1940 => KRASSOWSKY_ELLIPSOID_B,
+            Ellipsoid::Airy1830 => AIRY1830_ELLIPSOID_B,
+            Ellipsoid::AiryModified => AIRY_MODIFIED_ELLIPSOID_B,
+            Ellipsoid::Bessel1841 => BESSEL1841_ELLIPSOID_B,
+            Ellipsoid::Clarke1866 => CLARKE1866_ELLIPSOID_B,
+            Ellipsoid::Clarke1880 => CLARKE1880_ELLIPSOID_B,
+        }
+    }
+
+    /// Flattening
+    pub fn f(&self) -> f64 {
+        match *self {
+            Ellipsoid::Wgs84 => WGS84_ELLIPSOID_F,
+            Ellipsoid::Grs80 => GRS80_ELLIPSOID_F,
+            Ellipsoid::Krassowsky1940 => KRASSOWSKY_ELLIPSOID_F,
+            Ellipsoid::Airy1830 => AIRY1830_ELLIPSOID_F,
+            Ellipsoid::AiryModified => AIRY_MODIFIED_ELLIPSOID_F,
+            Ellipsoid::Bessel1841 => BESSEL1841_ELLIPSOID_F,
+            Ellipsoid::Clarke1866 => CLARKE1866_ELLIPSOID_F,
+            Ellipsoid::Clarke1880 => CLARKE1880_ELLIPSOID_F,
+        }
+    }
+}
+
@@ -1,6 +1,7 @@
 use std::error::Error;
 use std::fmt;
 use Lat;
+use Lon;
 
 #[derive(Debug, Clone)]
 pub enum Errors {
@@ -10,19 +11,27 @@ pub enum Errors {
     InvalidNorthingChar(char),
     InvalidEastingChar(char),
     InvalidLatitude(Lat),
+    InvalidLongitude(Lon),
     InvalidLatitudeBand(char),
     InvalidDatum(String),
     InvalidHemisphereChar(char),
+    InvalidZone(u8),
+    ZoneOutOfRange(u8),
+    InvalidGridReference(String),
+    OutsideGrid,
+    TruncatedReference,
+    UnknownStatePlaneZone(u16),
+    TooManyGridLines(usize),
 }
 
 impl fmt::Display for Errors {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", self.description())
+        writeln!(f, "{}", self.as_str())
     }
 }
 
-impl Error for Errors {
-    fn description(&self) -> &str {
+impl Errors {
+    fn as_str(&self) -> &'static str {
         match *self {
             Errors::InvalidZoneLetter(..)   => "invalid zone letter",
             Errors::InvalidColLetter(..)   => "invalid column letter",
@@ -30,13 +39,23 @@ impl Error for Errors {
             Errors::InvalidNorthingChar(..) => "MGRS point given invalid northing",
             Errors::InvalidEastingChar(..) => "MGRS point given invalid easting",
             Errors::InvalidLatitude(..) => "latitude outside UTM limits",
+            Errors::InvalidLongitude(..) => "invalid longitude",
             Errors::InvalidLatitudeBand(..) => "invalid Latitude band letter",
             Errors::InvalidDatum(..) => "invalid map datum was supplied",
             Errors::InvalidHemisphereChar(..) => "invalid hemisphere character",
+            Errors::InvalidZone(..) => "invalid UTM zone number",
+            Errors::ZoneOutOfRange(..) => "point too far from the central meridian of the UTM zone",
+            Errors::InvalidGridReference(..) => "invalid grid reference",
+            Errors::OutsideGrid => "point lies outside the extent of the grid",
+            Errors::TruncatedReference => "truncated grid reference needs a context point to resolve",
+            Errors::UnknownStatePlaneZone(..) => "no State Plane zone with the FIPS code",
+            Errors::TooManyGridLines(..) => "too many grid lines for the viewport at this accuracy",
         }
     }
+}
 
-    fn cause(&self) -> Option<&Error> {
-        None
+impl Error for Errors {
+    fn description(&self) -> &str {
+        self.as_str()
     }
 }
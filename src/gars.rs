@@ -0,0 +1,264 @@
+use std::str::FromStr;
+use std::fmt;
+
+use Mgrs;
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use polygon;
+
+// latitude band letters, A-Z without I and O
+const LETTERS: &[u8; 24] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// Size of a GARS cell
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GarsPrecision {
+    /// 30' x 30' cell, such as `006AG`
+    Cell,
+    /// 15' x 15' quadrant, such as `006AG3`
+    Quadrant,
+    /// 5' x 5' keypad, such as `006AG39`
+    Keypad,
+}
+
+impl GarsPrecision {
+    /// Size of the cell in degrees of latitude and longitude
+    pub fn as_degrees(&self) -> f64 {
+        match *self {
+            GarsPrecision::Cell => 0.5,
+            GarsPrecision::Quadrant => 0.25,
+            GarsPrecision::Keypad => 5.0 / 60.0,
+        }
+    }
+}
+
+/// Global Area Reference System cell, such as `006AG39`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Gars {
+    /// 30' longitude band (1..720 inclusive, eastward from 180°W)
+    pub lon_band: u16,
+    /// 30' latitude band (0..359 inclusive, northward from 90°S, written `AA`..`QZ`)
+    pub lat_band: u16,
+    /// 15' quadrant within the cell (1 NW, 2 NE, 3 SW, 4 SE)
+    pub quadrant: Option<u8>,
+    /// 5' keypad within the quadrant (1..9 inclusive, from the NW corner row by row)
+    pub keypad: Option<u8>,
+}
+
+impl Gars {
+    pub fn from_ll(ll: &LatLon, precision: GarsPrecision) -> Self {
+        /*!
+        Returns the GARS cell containing a WGS84 latitude/longitude.
+
+        ### Params
+         * **ll**: The point to convert.
+         * **precision**: Size of the cell to return.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(51.4778, -0.0015).unwrap();
+        let gars = Gars::from_ll(&ll, GarsPrecision::Keypad); // gars.to_string() = '360MU23'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        // count 5' steps from the south-west corner of the world, keeping the north pole and
+        // antimeridian in the last bands
+        let x = f64::floor((ll.lon + 180.0).rem_euclid(360.0) * 12.0) as u16;
+        let y = f64::min(f64::floor((ll.lat + 90.0) * 12.0), 180.0 * 12.0 - 1.0) as u16;
+
+        let mut gars = Gars {
+            lon_band: x / 6 + 1,
+            lat_band: y / 6,
+            quadrant: None,
+            keypad: None,
+        };
+        if precision != GarsPrecision::Cell {
+            // quadrants are numbered from the north-west
+            let (qx, qy) = ((x % 6) / 3, (y % 6) / 3);
+            gars.quadrant = Some((1 - qy as u8) * 2 + qx as u8 + 1);
+            if precision == GarsPrecision::Keypad {
+                let (kx, ky) = (x % 3, y % 3);
+                gars.keypad = Some((2 - ky as u8) * 3 + kx as u8 + 1);
+            }
+        }
+        gars
+    }
+
+    pub fn precision(&self) -> GarsPrecision {
+        match (self.quadrant, self.keypad) {
+            (None, _) => GarsPrecision::Cell,
+            (Some(_), None) => GarsPrecision::Quadrant,
+            (Some(_), Some(_)) => GarsPrecision::Keypad,
+        }
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Returns the extent of this cell.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values.
+        */
+
+        let mut lon = (self.lon_band - 1) as f64 * 0.5 - 180.0;
+        let mut lat = self.lat_band as f64 * 0.5 - 90.0;
+        if let Some(q) = self.quadrant {
+            let q = q - 1;
+            lon += (q % 2) as f64 * 0.25;
+            lat += (1 - q / 2) as f64 * 0.25;
+        }
+        if let Some(k) = self.keypad {
+            let k = k - 1;
+            lon += (k % 3) as f64 * 5.0 / 60.0;
+            lat += (2 - k / 3) as f64 * 5.0 / 60.0;
+        }
+
+        let size = self.precision().as_degrees();
+        [LatLon { lat, lon, ..Default::default() },
+         LatLon { lat: lat + size, lon: lon + size, ..Default::default() }]
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Returns the center of this cell.
+        */
+
+        let rect = self.to_ll_rect();
+        LatLon {
+            lat: (rect[0].lat + rect[1].lat) / 2.0,
+            lon: (rect[0].lon + rect[1].lon) / 2.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn overlaps(&self, mgrs: &Mgrs) -> bool {
+        /*!
+        Returns whether this cell and an MGRS grid reference's cell share any area.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let gars = Gars::from_ll(&mgrs.to_ll(), GarsPrecision::Keypad);
+        assert!(gars.overlaps(&mgrs));
+        ```
+        */
+
+        let clipped = polygon::clip_to_rect(&mgrs.to_ll_polygon(), &self.to_ll_rect());
+        polygon::area(&clipped) > 0.0
+    }
+
+    pub fn covering(mgrs: &Mgrs, precision: GarsPrecision) -> Vec<Gars> {
+        /*!
+        Returns the GARS cells overlapping an MGRS grid reference's cell.
+
+        ### Params
+         * **mgrs**: The grid reference.
+         * **precision**: Size of the GARS cells to return.
+
+        ### Return
+         * The overlapping cells, row by row from the south-west.
+        */
+
+        let rect = mgrs.to_ll_rect();
+        let size = precision.as_degrees();
+        let mut cells = Vec::new();
+        let mut lat = f64::floor(rect[0].lat / size) * size;
+        while lat < rect[1].lat {
+            let mut lon = f64::floor(rect[0].lon / size) * size;
+            while lon < rect[1].lon {
+                let ll = LatLon { lat: lat + size / 2.0, lon: lon + size / 2.0, ..Default::default() };
+                let gars = Gars::from_ll(&ll, precision);
+                if gars.overlaps(mgrs) { cells.push(gars); }
+                lon += size;
+            }
+            lat += size;
+        }
+        cells
+    }
+}
+
+impl From<Gars> for LatLon {
+    fn from(gars: Gars) -> Self {
+        gars.to_ll()
+    }
+}
+
+impl FromStr for Gars {
+    type Err = Errors;
+    /// Parses a GARS cell of 5, 6 or 7 characters, such as `006AG`, `006AG3` or `006AG39`.
+    fn from_str(gars: &str) -> Result<Self, Self::Err> {
+        let invalid = || Errors::InvalidGridReference(gars.to_owned());
+        let trimmed = gars.trim();
+        let b = trimmed.as_bytes();
+        if b.len() < 5 || b.len() > 7 || !trimmed.is_ascii() { return Err(invalid()); }
+
+        let lon_band = match trimmed[..3].parse::<u16>() {
+            Ok(n) if (1..=720).contains(&n) && b[..3].iter().all(|c| c.is_ascii_digit()) => n,
+            _ => return Err(invalid()),
+        };
+        let letter = |c: u8| LETTERS.iter().position(|l| *l == c.to_ascii_uppercase()).map(|i| i as u16);
+        let lat_band = match (letter(b[3]), letter(b[4])) {
+            (Some(l1), Some(l2)) if l1 * 24 + l2 < 360 => l1 * 24 + l2,
+            _ => return Err(invalid()),
+        };
+        let digit = |i: usize, max: u8| match b.get(i) {
+            Some(c) if b'1' <= *c && *c <= b'0' + max => Ok(Some(c - b'0')),
+            Some(_) => Err(invalid()),
+            None => Ok(None),
+        };
+
+        Ok(Gars {
+            lon_band,
+            lat_band,
+            quadrant: digit(5, 4)?,
+            keypad: digit(6, 9)?,
+        })
+    }
+}
+
+impl fmt::Display for Gars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let l1 = LETTERS[(self.lat_band / 24) as usize] as char;
+        let l2 = LETTERS[(self.lat_band % 24) as usize] as char;
+        write!(f, "{:03}{}{}", self.lon_band, l1, l2)?;
+        if let Some(q) = self.quadrant { write!(f, "{}", q)?; }
+        if let Some(k) = self.keypad { write!(f, "{}", k)?; }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Gars, GarsPrecision};
+    use latlon::LatLon;
+    use Mgrs;
+
+    #[test]
+    fn parse_and_format() {
+        let gars: Gars = "006AG39".parse().unwrap();
+        assert_eq!(&*gars.to_string(), "006AG39");
+        let rect = gars.to_ll_rect();
+        assert!((rect[0].lon - (-177.5 + 10.0 / 60.0)).abs() < 1e-12);
+        assert!((rect[0].lat - -87.0).abs() < 1e-12);
+        assert_eq!(Gars::from_ll(&gars.to_ll(), GarsPrecision::Keypad), gars);
+
+        let ll = LatLon::new(51.4778, -0.0015).unwrap();
+        assert_eq!(&*Gars::from_ll(&ll, GarsPrecision::Cell).to_string(), "360MU");
+        assert_eq!(&*Gars::from_ll(&ll, GarsPrecision::Quadrant).to_string(), "360MU2");
+
+        assert!("721AA".parse::<Gars>().is_err());
+        assert!("006RA".parse::<Gars>().is_err());
+        assert!("006AG5".parse::<Gars>().is_err());
+    }
+
+    #[test]
+    fn overlaps_mgrs() {
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let gars = Gars::from_ll(&mgrs.to_ll(), GarsPrecision::Keypad);
+        assert!(gars.overlaps(&mgrs));
+        assert_eq!(Gars::covering(&mgrs, GarsPrecision::Keypad), vec![gars]);
+    }
+}
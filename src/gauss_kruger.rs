@@ -0,0 +1,295 @@
+use std::fmt;
+
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use tm::TransverseMercator;
+
+/// Width of the longitudinal zones of a Gauss-Krüger grid
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ZoneWidth {
+    /// 3° zones, zone `n` has its central meridian at `3n`° (Germany, China large scale maps)
+    Three,
+    /// 6° zones, zone `n` has its central meridian at `6n - 3`° (Russia, China, eastern Europe)
+    Six,
+}
+
+impl ZoneWidth {
+    pub fn as_degrees(&self) -> f64 {
+        match *self {
+            ZoneWidth::Three => 3.0,
+            ZoneWidth::Six => 6.0,
+        }
+    }
+
+    pub fn zone_for_lon(&self, lon: f64) -> u8 {
+        /*!
+        Returns the zone a longitude falls within, counting eastward from Greenwich.
+        */
+
+        // zones are numbered eastward from 0°, so normalise to 0..360
+        let lon = (lon % 360.0 + 360.0) % 360.0;
+        match *self {
+            ZoneWidth::Three => {
+                let zone = f64::floor((lon + 1.5) / 3.0) as u8;
+                if zone == 0 { 120 } else { zone }
+            },
+            ZoneWidth::Six => f64::floor(lon / 6.0) as u8 + 1,
+        }
+    }
+
+    pub fn central_meridian(&self, zone: u8) -> f64 {
+        /*!
+        Returns the longitude of the central meridian of `zone`, in -180..180.
+        */
+
+        let lon = match *self {
+            ZoneWidth::Three => 3.0 * zone as f64,
+            ZoneWidth::Six => 6.0 * zone as f64 - 3.0,
+        };
+        if lon > 180.0 { lon - 360.0 } else { lon }
+    }
+
+    fn max_zone(&self) -> u8 {
+        match *self {
+            ZoneWidth::Three => 120,
+            ZoneWidth::Six => 60,
+        }
+    }
+}
+
+/// A Gauss-Krüger grid coordinate
+///
+/// Gauss-Krüger grids are Transverse Mercator zones with a unit central scale factor and a false
+/// easting of 500km, conventionally written with the zone number prefixed to the easting (e.g.
+/// zone 7, easting 500000 is written `7500000`).
+#[derive(Copy, Clone, Debug)]
+pub struct GaussKruger {
+    /// Longitudinal zone number
+    pub zone: u8,
+    /// Width of the zones this coordinate is expressed in
+    pub width: ZoneWidth,
+    /// Easting in metres from the false easting (500km west of the central meridian), without
+    /// the zone prefix
+    pub easting: f64,
+    /// Northing in metres from the equator
+    pub northing: f64,
+    /// Datum the coordinate is based on
+    pub datum: Datum,
+}
+
+impl GaussKruger {
+    pub fn from_ll(ll: &LatLon, width: ZoneWidth) -> Self {
+        /*!
+        Converts latitude/longitude to a Gauss-Krüger coordinate in the zone the point falls
+        within.
+
+        The projection uses the ellipsoid of `ll.datum`; convert with `LatLon::to_datum` first to
+        produce coordinates on another datum, e.g. `Datum::Pulkovo1942` or, for the German grid,
+        `Datum::Dhdn`.
+
+        ### Params
+         * **ll**: The point to convert.
+         * **width**: Whether to use 3° or 6° zones.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(55.7539, 37.6208).unwrap().to_datum(Datum::Pulkovo1942);
+        let gk = GaussKruger::from_ll(&ll, ZoneWidth::Six);
+        assert_eq!(gk.zone, 7);
+        ```
+        */
+
+        GaussKruger::from_ll_in_zone(ll, width, width.zone_for_lon(ll.lon))
+            .expect("zone_for_lon returned an invalid zone")
+    }
+
+    pub fn from_ll_in_zone(ll: &LatLon, width: ZoneWidth, zone: u8) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to a Gauss-Krüger coordinate in a forced zone.
+
+        ### Params
+         * **ll**: The point to convert.
+         * **width**: Whether to use 3° or 6° zones.
+         * **zone**: The zone to project into.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::InvalidZone` if `zone` is not a valid zone number for `width`.
+        */
+
+        if !(1 <= zone && zone <= width.max_zone()) { return Err(Errors::InvalidZone(zone)); }
+
+        let mut gk = GaussKruger {
+            zone,
+            width,
+            easting: 0.0,
+            northing: 0.0,
+            datum: ll.datum,
+        };
+        let p = gk.projection().forward(ll);
+        gk.easting = p.easting;
+        gk.northing = p.northing;
+        Ok(gk)
+    }
+
+    pub fn from_prefixed(easting: f64, northing: f64, width: ZoneWidth, datum: Datum) -> Result<Self, Errors> {
+        /*!
+        Creates a Gauss-Krüger coordinate from an easting carrying the zone number prefix.
+
+        ### Params
+         * **easting**: Easting in metres, prefixed with the zone number (e.g. `7413161.5`).
+         * **northing**: Northing in metres.
+         * **width**: Whether the coordinate is in 3° or 6° zones.
+         * **datum**: Datum the coordinate is based on.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::InvalidZone` if the prefix is not a valid zone number for `width`.
+        */
+
+        let zone = f64::floor(easting / 1e6);
+        if !(1.0 <= zone && zone <= width.max_zone() as f64) {
+            return Err(Errors::InvalidZone(zone as u8));
+        }
+        Ok(GaussKruger {
+            zone: zone as u8,
+            width,
+            easting: easting - zone * 1e6,
+            northing,
+            datum,
+        })
+    }
+
+    pub fn prefixed_easting(&self) -> f64 {
+        /*!
+        Returns the easting with the zone number prefixed, as written on Gauss-Krüger maps.
+        */
+
+        self.zone as f64 * 1e6 + self.easting
+    }
+
+    pub fn projection(&self) -> TransverseMercator {
+        /*!
+        Returns the Transverse Mercator projection this coordinate's zone is defined by.
+        */
+
+        TransverseMercator {
+            lon0: self.width.central_meridian(self.zone),
+            lat0: 0.0,
+            k0: 1.0,
+            false_easting: 500e3,
+            false_northing: 0.0,
+            ellipsoid: self.datum.ellipsoid(),
+        }
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this coordinate to latitude/longitude on the coordinate's datum.
+        */
+
+        let mut ll = self.projection().inverse(self.easting, self.northing);
+        ll.datum = self.datum;
+        ll
+    }
+
+    fn as_string(&self, digits: usize) -> String {
+        /*!
+        Returns a string representation of a Gauss-Krüger coordinate as the zone-prefixed easting
+        and the northing.
+
+        ### Params
+         * **digits** Determines the number of digits to return after the decimal
+        */
+
+        format!("{0:.2$} {1:.2$}", self.prefixed_easting(), self.northing, digits)
+    }
+}
+
+impl From<GaussKruger> for LatLon {
+    fn from(gk: GaussKruger) -> Self {
+        gk.to_ll()
+    }
+}
+
+impl fmt::Display for GaussKruger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GaussKruger, ZoneWidth};
+    use Errors;
+    use datum::Datum;
+    use latlon::LatLon;
+
+    // Reference values from the Transverse Mercator series in the Ordnance Survey's 'A guide to
+    // coordinate systems in Great Britain', Annex C, evaluated on the Krassowsky 1940 and Bessel
+    // 1841 ellipsoids
+
+    fn ll(lat: f64, lon: f64, datum: Datum) -> LatLon {
+        LatLon { lat, lon, datum, ..Default::default() }
+    }
+
+    #[test]
+    fn pulkovo_six_degree() {
+        let gk = GaussKruger::from_ll(&ll(55.7539, 37.6208, Datum::Pulkovo1942), ZoneWidth::Six);
+        assert_eq!(gk.zone, 7);
+        assert!((gk.prefixed_easting() - 7413403.49).abs() < 0.01);
+        assert!((gk.northing - 6182132.27).abs() < 0.01);
+        assert_eq!(&*gk.to_string(), "7413403 6182132");
+    }
+
+    #[test]
+    fn pulkovo_three_degree() {
+        let gk = GaussKruger::from_ll(&ll(55.7539, 35.0, Datum::Pulkovo1942), ZoneWidth::Three);
+        assert_eq!(gk.zone, 12);
+        assert!((gk.prefixed_easting() - 12437211.46).abs() < 0.01);
+        assert!((gk.northing - 6181723.59).abs() < 0.01);
+    }
+
+    #[test]
+    fn dhdn_three_degree() {
+        let gk = GaussKruger::from_ll(&ll(51.0, 13.4, Datum::Dhdn), ZoneWidth::Three);
+        assert_eq!(gk.zone, 4);
+        assert!((gk.prefixed_easting() - 4598262.73).abs() < 0.01);
+        assert!((gk.northing - 5652438.62).abs() < 0.01);
+    }
+
+    #[test]
+    fn prefix_round_trip() {
+        let gk = GaussKruger::from_prefixed(7413403.49, 6182132.27, ZoneWidth::Six, Datum::Pulkovo1942).unwrap();
+        assert_eq!(gk.zone, 7);
+        assert!((gk.easting - 413403.49).abs() < 1e-6);
+        assert!((gk.prefixed_easting() - 7413403.49).abs() < 1e-6);
+        let back = gk.to_ll();
+        assert!((back.lat - 55.7539).abs() < 1e-7);
+        assert!((back.lon - 37.6208).abs() < 1e-7);
+
+        let gk = GaussKruger::from_prefixed(12437211.46, 6181723.59, ZoneWidth::Three, Datum::Pulkovo1942).unwrap();
+        assert_eq!(gk.zone, 12);
+        assert!((gk.to_ll().lon - 35.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn invalid_zone() {
+        match GaussKruger::from_prefixed(61500000.0, 0.0, ZoneWidth::Six, Datum::Pulkovo1942) {
+            Err(Errors::InvalidZone(61)) => (),
+            r => panic!("expected InvalidZone, got {:?}", r),
+        }
+        let p = ll(55.7539, 37.6208, Datum::Pulkovo1942);
+        match GaussKruger::from_ll_in_zone(&p, ZoneWidth::Six, 0) {
+            Err(Errors::InvalidZone(0)) => (),
+            r => panic!("expected InvalidZone, got {:?}", r),
+        }
+        match GaussKruger::from_ll_in_zone(&p, ZoneWidth::Three, 121) {
+            Err(Errors::InvalidZone(121)) => (),
+            r => panic!("expected InvalidZone, got {:?}", r),
+        }
+        assert_eq!(GaussKruger::from_ll_in_zone(&p, ZoneWidth::Six, 8).unwrap().zone, 8);
+    }
+}
@@ -0,0 +1,276 @@
+use std::str::FromStr;
+use std::fmt;
+
+use Accuracy;
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use direction::Direction;
+
+// geohash alphabet, the digits and lowercase letters except a, i, l and o
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+// longest geohash whose cell is still coarser than f64 precision
+const MAX_LEN: usize = 12;
+
+/// Geohash, such as `u09tunqg`
+///
+/// Geohashes interleave the bits of the longitude and latitude, so each further character splits
+/// the cell into 32.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Geohash {
+    hash: String,
+}
+
+impl Geohash {
+    pub fn encode(ll: &LatLon, len: usize) -> Self {
+        /*!
+        Encodes a WGS84 latitude/longitude as a geohash.
+
+        ### Params
+         * **ll**: The point to encode.
+         * **len**: Number of characters (1..12 inclusive).
+
+        # Panics
+
+        If `len` is outside 1..12.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        let geohash = Geohash::encode(&ll, 8); // geohash.to_string() = 'u09tunqg'
+        ```
+        */
+
+        if !(1..=MAX_LEN).contains(&len) { panic!("Invalid geohash length {}", len); }
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        let lon = (ll.lon + 180.0).rem_euclid(360.0) - 180.0;
+        let (mut lat_min, mut lat_max) = (-90.0, 90.0);
+        let (mut lon_min, mut lon_max) = (-180.0, 180.0);
+
+        let mut hash = String::with_capacity(len);
+        let mut even_bit = true; // bits alternate longitude, latitude, starting with longitude
+        while hash.len() < len {
+            let mut idx = 0;
+            for _ in 0..5 {
+                idx <<= 1;
+                if even_bit {
+                    let mid = (lon_min + lon_max) / 2.0;
+                    if lon >= mid { idx |= 1; lon_min = mid; } else { lon_max = mid; }
+                } else {
+                    let mid = (lat_min + lat_max) / 2.0;
+                    if ll.lat >= mid { idx |= 1; lat_min = mid; } else { lat_max = mid; }
+                }
+                even_bit = !even_bit;
+            }
+            hash.push(BASE32[idx] as char);
+        }
+        Geohash { hash }
+    }
+
+    pub fn from_ll_with_accuracy(ll: &LatLon, accuracy: Accuracy) -> Self {
+        /*!
+        Encodes a point as a geohash with cells of about the size of an MGRS cell at `accuracy`.
+        See `Accuracy::as_geohash_len`.
+        */
+
+        Geohash::encode(ll, accuracy.as_geohash_len())
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Returns the cell of this geohash.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values.
+        */
+
+        let (mut lat_min, mut lat_max) = (-90.0, 90.0);
+        let (mut lon_min, mut lon_max) = (-180.0, 180.0);
+
+        let mut even_bit = true;
+        for b in self.hash.bytes() {
+            let idx = BASE32.iter().position(|c| *c == b).expect("geohash character out of alphabet");
+            for n in (0..5).rev() {
+                let bit = (idx >> n) & 1;
+                if even_bit {
+                    let mid = (lon_min + lon_max) / 2.0;
+                    if bit == 1 { lon_min = mid; } else { lon_max = mid; }
+                } else {
+                    let mid = (lat_min + lat_max) / 2.0;
+                    if bit == 1 { lat_min = mid; } else { lat_max = mid; }
+                }
+                even_bit = !even_bit;
+            }
+        }
+
+        [LatLon { lat: lat_min, lon: lon_min, ..Default::default() },
+         LatLon { lat: lat_max, lon: lon_max, ..Default::default() }]
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Decodes this geohash to the centre of its cell.
+        */
+
+        let rect = self.to_ll_rect();
+        LatLon {
+            lat: (rect[0].lat + rect[1].lat) / 2.0,
+            lon: (rect[0].lon + rect[1].lon) / 2.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn error(&self) -> (f64, f64) {
+        /*!
+        Returns the precision of this geohash as the greatest latitude and longitude error of a
+        point decoded to the centre of its cell, in degrees.
+        */
+
+        let rect = self.to_ll_rect();
+        ((rect[1].lat - rect[0].lat) / 2.0, (rect[1].lon - rect[0].lon) / 2.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.hash.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hash.is_empty()
+    }
+
+    pub fn accuracy(&self) -> Accuracy {
+        /*!
+        Returns the MGRS `Accuracy` whose cells are of about the size of this geohash's cell. See
+        `Accuracy::from_geohash_len`.
+        */
+
+        Accuracy::from_geohash_len(self.len())
+    }
+
+    pub fn neighbour(&self, direction: Direction) -> Option<Geohash> {
+        /*!
+        Returns the adjacent geohash of the same length.
+
+        ### Returns
+         * **Some**: The neighbouring geohash, wrapping around the antimeridian.
+         * **None**: If the neighbour would lie beyond a pole.
+        */
+
+        let rect = self.to_ll_rect();
+        let centre = self.to_ll();
+        let (de, dn) = direction.as_offset();
+        let lat = centre.lat + dn as f64 * (rect[1].lat - rect[0].lat);
+        if !(-90.0 < lat && lat < 90.0) { return None; }
+
+        let ll = LatLon {
+            lat,
+            lon: centre.lon + de as f64 * (rect[1].lon - rect[0].lon),
+            ..Default::default()
+        };
+        Some(Geohash::encode(&ll, self.len()))
+    }
+
+    pub fn neighbours(&self) -> Vec<Geohash> {
+        /*!
+        Returns the adjacent geohashes of the same length, clockwise from north, leaving out any
+        beyond a pole.
+        */
+
+        Direction::all().iter().filter_map(|d| self.neighbour(*d)).collect()
+    }
+}
+
+impl From<Geohash> for LatLon {
+    fn from(geohash: Geohash) -> Self {
+        geohash.to_ll()
+    }
+}
+
+impl FromStr for Geohash {
+    type Err = Errors;
+    /// Parses a geohash of 1..12 characters, in either case.
+    fn from_str(geohash: &str) -> Result<Self, Self::Err> {
+        let hash = geohash.to_ascii_lowercase();
+        if hash.is_empty() || hash.len() > MAX_LEN || !hash.bytes().all(|b| BASE32.contains(&b)) {
+            return Err(Errors::InvalidGridReference(geohash.to_owned()));
+        }
+        Ok(Geohash { hash })
+    }
+}
+
+impl fmt::Display for Geohash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.hash)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Geohash;
+    use Accuracy;
+    use latlon::LatLon;
+    use direction::Direction;
+
+    #[test]
+    fn encode() {
+        let ll = LatLon::new(57.64911, 10.40744).unwrap();
+        assert_eq!(&*Geohash::encode(&ll, 11).to_string(), "u4pruydqqvj");
+        assert_eq!(&*Geohash::encode(&ll, 1).to_string(), "u");
+
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        let geohash = Geohash::encode(&ll, 8);
+        assert_eq!(&*geohash.to_string(), "u09tunqg");
+        assert_eq!(geohash.len(), 8);
+        assert!(!geohash.is_empty());
+        assert_eq!(geohash.accuracy(), Accuracy::Ten);
+        assert_eq!(&*Geohash::from_ll_with_accuracy(&ll, Accuracy::OneHundred).to_string(), "u09tunq");
+    }
+
+    #[test]
+    fn decode_and_bounds() {
+        let geohash: Geohash = "EZS42".parse().unwrap();
+        assert_eq!(&*geohash.to_string(), "ezs42");
+        let rect = geohash.to_ll_rect();
+        assert_eq!(rect[0].lat, 42.5830078125);
+        assert_eq!(rect[0].lon, -5.625);
+        assert_eq!(rect[1].lat, 42.626953125);
+        assert_eq!(rect[1].lon, -5.5810546875);
+
+        let ll = geohash.to_ll();
+        assert!((ll.lat - 42.605).abs() < 1e-3);
+        assert!((ll.lon - -5.603).abs() < 1e-3);
+        let (lat_err, lon_err) = geohash.error();
+        assert!((lat_err - 0.02197265625).abs() < 1e-12);
+        assert!((lon_err - 0.02197265625).abs() < 1e-12);
+
+        assert!("".parse::<Geohash>().is_err());
+        assert!("ezs42a".parse::<Geohash>().is_err());
+        assert!("u4pruydqqvjxx".parse::<Geohash>().is_err());
+    }
+
+    fn neighbours(geohash: &str) -> Vec<String> {
+        geohash.parse::<Geohash>().unwrap().neighbours().iter().map(|g| g.to_string()).collect()
+    }
+
+    #[test]
+    fn neighbours_clockwise_from_north() {
+        assert_eq!(neighbours("gbsuv"), vec!["gbsvj", "gbsvn", "gbsuy", "gbsuw", "gbsut", "gbsus", "gbsuu", "gbsvh"]);
+    }
+
+    #[test]
+    fn neighbours_across_antimeridian() {
+        // xbpbp touches the antimeridian just north of the equator
+        assert_eq!(neighbours("xbpbp"), vec!["xbpbr", "80002", "80000", "2pbpb", "rzzzz", "rzzzy", "xbpbn", "xbpbq"]);
+        let geohash: Geohash = "80000".parse().unwrap();
+        assert_eq!(&*geohash.neighbour(Direction::W).unwrap().to_string(), "xbpbp");
+    }
+
+    #[test]
+    fn neighbours_at_pole() {
+        let geohash: Geohash = "upb".parse().unwrap();
+        assert!(geohash.neighbour(Direction::N).is_none());
+        assert_eq!(neighbours("upb"), vec!["upc", "up9", "up8", "gzx", "gzz"]);
+    }
+}
@@ -0,0 +1,258 @@
+use std::str::FromStr;
+use std::fmt;
+
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+
+// 15° longitude and latitude tile letters, A-Z without I and O, and A-M without I
+const LON_TILES: &[u8; 24] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const LAT_TILES: &[u8; 12] = b"ABCDEFGHJKLM";
+// 1° letters within a tile, A-Q without I and O
+const DEGREES: &[u8; 15] = b"ABCDEFGHJKLMNPQ";
+// positions are held in hundredths of a minute from 180°W and 90°S
+const PER_DEGREE: u32 = 6000;
+const PER_TILE: u32 = 15 * PER_DEGREE;
+
+/// Precision of a GEOREF reference
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GeorefPrecision {
+    /// 15° tile, such as `MK`
+    Tile,
+    /// 1° quadrangle, such as `MKML`
+    Degree,
+    /// 1', such as `MKML1234`
+    Minute,
+    /// 0.1', such as `MKML123345`
+    TenthMinute,
+    /// 0.01', such as `MKML12343456`
+    HundredthMinute,
+}
+
+impl GeorefPrecision {
+    /// Number of digits given for each of the longitude and latitude minutes
+    pub fn as_num_digits(&self) -> usize {
+        match *self {
+            GeorefPrecision::Tile | GeorefPrecision::Degree => 0,
+            GeorefPrecision::Minute => 2,
+            GeorefPrecision::TenthMinute => 3,
+            GeorefPrecision::HundredthMinute => 4,
+        }
+    }
+
+    /// Size of the cell in hundredths of a minute
+    fn as_step(&self) -> u32 {
+        match *self {
+            GeorefPrecision::Tile => PER_TILE,
+            GeorefPrecision::Degree => PER_DEGREE,
+            _ => u32::pow(10, 4 - self.as_num_digits() as u32),
+        }
+    }
+
+    fn from_len(len: usize) -> Option<Self> {
+        match len {
+            2 => Some(GeorefPrecision::Tile),
+            4 => Some(GeorefPrecision::Degree),
+            8 => Some(GeorefPrecision::Minute),
+            10 => Some(GeorefPrecision::TenthMinute),
+            12 => Some(GeorefPrecision::HundredthMinute),
+            _ => None,
+        }
+    }
+}
+
+/// World Geographic Reference System reference, such as `MKML1234`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Georef {
+    // south-west corner of the cell, in hundredths of a minute from 180°W and 90°S
+    lon: u32,
+    lat: u32,
+    precision: GeorefPrecision,
+}
+
+impl Georef {
+    pub fn from_ll(ll: &LatLon, precision: GeorefPrecision) -> Self {
+        /*!
+        Returns the GEOREF cell containing a WGS84 latitude/longitude.
+
+        ### Params
+         * **ll**: The point to convert.
+         * **precision**: Size of the cell to return.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(55.5708, -3.7917).unwrap();
+        let georef = Georef::from_ll(&ll, GeorefPrecision::Minute); // georef.to_string() = 'MKML1234'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        // keep the north pole and antimeridian in the last cells
+        let lon = f64::floor((ll.lon + 180.0).rem_euclid(360.0) * PER_DEGREE as f64) as u32;
+        let lat = f64::min(f64::floor((ll.lat + 90.0) * PER_DEGREE as f64), (180 * PER_DEGREE - 1) as f64) as u32;
+
+        let step = precision.as_step();
+        Georef {
+            lon: lon.min(360 * PER_DEGREE - 1) / step * step,
+            lat: lat / step * step,
+            precision,
+        }
+    }
+
+    pub fn precision(&self) -> GeorefPrecision {
+        self.precision
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Returns the extent of this cell.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values.
+        */
+
+        let step = self.precision.as_step();
+        let degrees = |v: u32| v as f64 / PER_DEGREE as f64;
+        [LatLon { lat: degrees(self.lat) - 90.0, lon: degrees(self.lon) - 180.0, ..Default::default() },
+         LatLon { lat: degrees(self.lat + step) - 90.0, lon: degrees(self.lon + step) - 180.0, ..Default::default() }]
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Returns the center of this cell.
+        */
+
+        let rect = self.to_ll_rect();
+        LatLon {
+            lat: (rect[0].lat + rect[1].lat) / 2.0,
+            lon: (rect[0].lon + rect[1].lon) / 2.0,
+            ..Default::default()
+        }
+    }
+
+    fn as_string(&self, precision: GeorefPrecision) -> String {
+        /*!
+        Returns a string representation of a GEOREF reference.
+
+        ### Params
+         * **precision** Precision of returned reference, no finer than this reference's own.
+
+        ### Returns
+         * This reference in standard, unseparated format.
+        */
+
+        let mut georef = String::with_capacity(12);
+        georef.push(LON_TILES[(self.lon / PER_TILE) as usize] as char);
+        georef.push(LAT_TILES[(self.lat / PER_TILE) as usize] as char);
+        if precision == GeorefPrecision::Tile { return georef; }
+
+        georef.push(DEGREES[(self.lon % PER_TILE / PER_DEGREE) as usize] as char);
+        georef.push(DEGREES[(self.lat % PER_TILE / PER_DEGREE) as usize] as char);
+        if precision == GeorefPrecision::Degree { return georef; }
+
+        // set required precision
+        let digits = precision.as_num_digits();
+        let lon = self.lon % PER_DEGREE / precision.as_step();
+        let lat = self.lat % PER_DEGREE / precision.as_step();
+        georef + &format!("{0:01$}{2:01$}", lon, digits, lat)
+    }
+}
+
+impl From<Georef> for LatLon {
+    fn from(georef: Georef) -> Self {
+        georef.to_ll()
+    }
+}
+
+impl FromStr for Georef {
+    type Err = Errors;
+    /// Parses a GEOREF reference of 2, 4, 8, 10 or 12 characters, in either case.
+    fn from_str(georef: &str) -> Result<Self, Self::Err> {
+        let invalid = || Errors::InvalidGridReference(georef.to_owned());
+        let normalised = georef.trim().to_ascii_uppercase();
+        let b = normalised.as_bytes();
+        let precision = GeorefPrecision::from_len(b.len()).ok_or_else(invalid)?;
+        if !normalised.is_ascii() { return Err(invalid()); }
+
+        let letter = |set: &[u8], i: usize| match b.get(i) {
+            Some(c) => set.iter().position(|l| l == c).map(|p| p as u32).ok_or_else(invalid),
+            None => Ok(0),
+        };
+        let mut lon = letter(LON_TILES, 0)? * PER_TILE + letter(DEGREES, 2)? * PER_DEGREE;
+        let mut lat = letter(LAT_TILES, 1)? * PER_TILE + letter(DEGREES, 3)? * PER_DEGREE;
+
+        let digits = precision.as_num_digits();
+        if digits > 0 {
+            let minutes = |s: &str| match s.parse::<u32>() {
+                Ok(m) if s.bytes().all(|c| c.is_ascii_digit()) && m < 60 * u32::pow(10, digits as u32 - 2) => Ok(m),
+                _ => Err(invalid()),
+            };
+            lon += minutes(&normalised[4..4 + digits])? * precision.as_step();
+            lat += minutes(&normalised[4 + digits..])? * precision.as_step();
+        }
+
+        Ok(Georef { lon, lat, precision })
+    }
+}
+
+impl fmt::Display for Georef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(self.precision))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Georef, GeorefPrecision};
+    use latlon::LatLon;
+
+    #[test]
+    fn format_every_precision() {
+        let ll = LatLon::new(55.5708, -3.7917).unwrap();
+        let format = |precision| Georef::from_ll(&ll, precision).to_string();
+        assert_eq!(&*format(GeorefPrecision::Tile), "MK");
+        assert_eq!(&*format(GeorefPrecision::Degree), "MKML");
+        assert_eq!(&*format(GeorefPrecision::Minute), "MKML1234");
+        assert_eq!(&*format(GeorefPrecision::TenthMinute), "MKML124342");
+        assert_eq!(&*format(GeorefPrecision::HundredthMinute), "MKML12493424");
+    }
+
+    #[test]
+    fn parse_every_precision() {
+        for s in &["MK", "MKML", "MKML1234", "MKML124342", "MKML12493424"] {
+            let georef: Georef = s.parse().unwrap();
+            assert_eq!(&*georef.to_string(), *s);
+        }
+        let georef: Georef = "mkml1234".parse().unwrap();
+        assert_eq!(georef.precision(), GeorefPrecision::Minute);
+
+        // south-west corner of the 1' cell, 3°48'W 55°34'N
+        let rect = georef.to_ll_rect();
+        assert!((rect[0].lon - -3.8).abs() < 1e-9);
+        assert!((rect[0].lat - (55.0 + 34.0 / 60.0)).abs() < 1e-9);
+        assert!((rect[1].lon - rect[0].lon - 1.0 / 60.0).abs() < 1e-9);
+
+        let tile: Georef = "MK".parse().unwrap();
+        let rect = tile.to_ll_rect();
+        assert_eq!(rect[0].lon, -15.0);
+        assert_eq!(rect[0].lat, 45.0);
+        assert_eq!(rect[1].lon, 0.0);
+        assert_eq!(rect[1].lat, 60.0);
+    }
+
+    #[test]
+    fn reject_invalid() {
+        // minutes run 0..60
+        assert!("MKML6034".parse::<Georef>().is_err());
+        assert!("MKML1260".parse::<Georef>().is_err());
+        assert!("MKML600342".parse::<Georef>().is_err());
+        assert!("MKML12496000".parse::<Georef>().is_err());
+        assert!("MKML5959".parse::<Georef>().is_ok());
+
+        assert!("MKM".parse::<Georef>().is_err());
+        assert!("MKMI".parse::<Georef>().is_err());
+        assert!("MNML".parse::<Georef>().is_err());
+        assert!("MKML12a4".parse::<Georef>().is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use Accuracy;
+use Errors;
+use gzd::{Gzd, GridSquareId100k};
+use latlon::LatLon;
+use datum::Datum;
+use col::ColLetter;
+use row::RowLetter;
+use tm::TransverseMercator;
+use covering::{zone_projection, grid_extent};
+use polygon;
+
+// grid lines are sampled every kilometre, or at most this many times for long lines
+const SAMPLE_METRES: f64 = 1e3;
+const MAX_SAMPLES: f64 = 100.0;
+// most lines generated for a viewport, about a million points
+pub const MAX_LINES: usize = 10000;
+
+/// What a grid line bounds
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GridLineKind {
+    /// Boundary between two UTM zones, along a meridian
+    Zone,
+    /// Boundary between two latitude bands, along a parallel
+    Band,
+    /// Boundary between two 100km squares
+    Square100k,
+    /// Line of the grid at the requested `Accuracy`, within a 100km square
+    Grid,
+}
+
+/// Text to draw on a map overlay, such as `31U`, `DQ` or `48`
+#[derive(Clone, Debug)]
+pub struct GridLabel {
+    pub text: String,
+    /// Point on the line to anchor the text to
+    pub ll: LatLon,
+}
+
+/// Line of an MGRS map overlay, see `Mgrs::grid_lines`
+#[derive(Clone, Debug)]
+pub struct GridLine {
+    pub kind: GridLineKind,
+    /// Points along the line; lines are curved in latitude/longitude, so these are close enough
+    /// together to be joined with straight segments
+    pub points: Vec<LatLon>,
+    /// Labels for what the line bounds:
+    ///  * zone and band lines: the GZD to the east or north, at the middle of the line
+    ///  * 100km square lines: the square to the east or north, at the middle of the line
+    ///  * grid lines: the easting or northing digits, at the start of the line
+    pub labels: Vec<GridLabel>,
+}
+
+pub fn for_viewport(viewport: &[LatLon; 2], accuracy: Accuracy) -> Result<Vec<GridLine>, Errors> {
+    /*!
+    Generates the lines of the grid within a viewport, GZD by GZD from 1C. See `Mgrs::grid_lines`.
+    */
+
+    let viewport = [viewport[0].to_datum(Datum::Wgs84), viewport[1].to_datum(Datum::Wgs84)];
+    let mut grids = Vec::new();
+    for gzd in Gzd::all() {
+        let rect = gzd.bounds().expect("all GZDs are valid");
+        let clipped = [
+            LatLon { lat: f64::max(rect[0].lat, viewport[0].lat), lon: f64::max(rect[0].lon, viewport[0].lon), ..Default::default() },
+            LatLon { lat: f64::min(rect[1].lat, viewport[1].lat), lon: f64::min(rect[1].lon, viewport[1].lon), ..Default::default() },
+        ];
+        if clipped[0].lat >= clipped[1].lat || clipped[0].lon >= clipped[1].lon { continue; }
+        grids.push((gzd, rect, ZoneGrid::new(&gzd, clipped)));
+    }
+
+    // count before generating, so a fine accuracy over a large viewport fails fast
+    let count: usize = grids.iter().map(|(_, _, grid)| grid.line_count(accuracy)).sum();
+    if count > MAX_LINES { return Err(Errors::TooManyGridLines(count)); }
+
+    let mut lines = Vec::new();
+    for (gzd, rect, grid) in grids {
+        gzd_lines(&mut lines, &gzd, &rect, &grid.rect);
+        grid.lines(&mut lines, accuracy);
+    }
+    Ok(lines)
+}
+
+fn gzd_lines(lines: &mut Vec<GridLine>, gzd: &Gzd, rect: &[LatLon; 2], clipped: &[LatLon; 2]) {
+    /*!
+    Adds the zone and band lines along the west and south edges of a GZD, and the north and east
+    edges at 84°N and 180°E, where they fall within the viewport.
+    */
+
+    let mut edge = |kind: GridLineKind, a: LatLon, b: LatLon| {
+        let pieces = polygon::clip_line_to_rect(&[a, b], clipped);
+        for points in pieces {
+            let labels = vec![GridLabel { text: gzd.to_string(), ll: midpoint(&points) }];
+            lines.push(GridLine { kind, points, labels });
+        }
+    };
+
+    let (sw, ne) = (rect[0], rect[1]);
+    let nw = LatLon { lat: ne.lat, ..sw };
+    let se = LatLon { lat: sw.lat, ..ne };
+    edge(GridLineKind::Zone, sw, nw);
+    edge(GridLineKind::Band, sw, se);
+    if ne.lon == 180.0 { edge(GridLineKind::Zone, se, ne); }
+    if ne.lat == 84.0 { edge(GridLineKind::Band, nw, ne); }
+}
+
+// the grid of a GZD, within the viewport
+struct ZoneGrid {
+    zone: u8,
+    tm: TransverseMercator,
+    rect: [LatLon; 2],
+    // easting/northing extent of the viewport within the GZD, as (min_e, min_n, max_e, max_n)
+    extent: (f64, f64, f64, f64),
+}
+
+impl ZoneGrid {
+    fn new(gzd: &Gzd, rect: [LatLon; 2]) -> Self {
+        let tm = zone_projection(gzd);
+        let corners = [rect[0], LatLon { lon: rect[1].lon, ..rect[0] },
+                       rect[1], LatLon { lon: rect[0].lon, ..rect[1] }];
+        ZoneGrid {
+            zone: gzd.zone,
+            tm,
+            rect,
+            extent: grid_extent(&tm, &corners),
+        }
+    }
+
+    fn line_count(&self, accuracy: Accuracy) -> usize {
+        /*!
+        Returns about how many lines `lines` adds: a line per 100km square edge, and one per
+        grid easting and northing.
+        */
+
+        let (min_e, min_n, max_e, max_n) = self.extent;
+        let square = 100e3;
+        let squares = (multiples(min_e, max_e, square).count() + 1) * (multiples(min_n, max_n, square).count() + 1) * 2;
+        let size = accuracy.as_distance() as f64;
+        if size >= square { return squares; }
+        squares + multiples(min_e, max_e, size).count() + multiples(min_n, max_n, size).count()
+    }
+
+    fn lines(&self, lines: &mut Vec<GridLine>, accuracy: Accuracy) {
+        /*!
+        Adds the 100km square and finer grid lines.
+        */
+
+        let (min_e, min_n, max_e, max_n) = self.extent;
+        let size = accuracy.as_distance() as f64;
+        let square = 100e3;
+
+        // 100km square boundaries, one line per square so each carries the square's letters
+        for e in multiples(min_e, max_e, square) {
+            for n in multiples(min_n - square, max_n, square) {
+                self.push(lines, GridLineKind::Square100k, (e, f64::max(n, min_n)), (e, f64::min(n + square, max_n)), |points| {
+                    vec![GridLabel { text: self.square_at(e, n).to_string(), ll: midpoint(points) }]
+                });
+            }
+        }
+        for n in multiples(min_n, max_n, square) {
+            for e in multiples(min_e - square, max_e, square) {
+                self.push(lines, GridLineKind::Square100k, (f64::max(e, min_e), n), (f64::min(e + square, max_e), n), |points| {
+                    vec![GridLabel { text: self.square_at(e, n).to_string(), ll: midpoint(points) }]
+                });
+            }
+        }
+        if size >= square { return; }
+
+        // finer lines, labelled with their digits within the 100km square
+        let digits = accuracy.as_num_digits() / 2;
+        let label = |v: f64| format!("{0:01$}", (v % square / size) as u32, digits);
+        for e in multiples(min_e, max_e, size).filter(|e| e % square != 0.0) {
+            self.push(lines, GridLineKind::Grid, (e, min_n), (e, max_n), |points| {
+                vec![GridLabel { text: label(e), ll: points[0] }]
+            });
+        }
+        for n in multiples(min_n, max_n, size).filter(|n| n % square != 0.0) {
+            self.push(lines, GridLineKind::Grid, (min_e, n), (max_e, n), |points| {
+                vec![GridLabel { text: label(n), ll: points[0] }]
+            });
+        }
+    }
+
+    fn push<L>(&self, lines: &mut Vec<GridLine>, kind: GridLineKind, from: (f64, f64), to: (f64, f64), labels: L)
+        where L: Fn(&[LatLon]) -> Vec<GridLabel> {
+        /*!
+        Adds the pieces of a straight grid line within the viewport.
+        */
+
+        let (de, dn) = (to.0 - from.0, to.1 - from.1);
+        // skip slivers where the line only touches the viewport
+        if de < 1.0 && dn < 1.0 { return; }
+        let steps = f64::ceil(f64::max(de, dn) / SAMPLE_METRES).clamp(1.0, MAX_SAMPLES) as usize;
+        let points: Vec<LatLon> = (0..steps + 1)
+            .map(|i| i as f64 / steps as f64)
+            .map(|t| self.tm.inverse(from.0 + t * de, from.1 + t * dn))
+            .collect();
+
+        for piece in polygon::clip_line_to_rect(&points, &self.rect) {
+            // a central meridian can run along the edge of the Norway and Svalbard GZDs, where
+            // the neighbouring GZD's lines take over
+            if along_edge(&piece, &self.rect) { continue; }
+            let labels = labels(&piece);
+            lines.push(GridLine { kind, points: piece, labels });
+        }
+    }
+
+    fn square_at(&self, easting: f64, northing: f64) -> GridSquareId100k {
+        GridSquareId100k {
+            col: ColLetter::from_zone_and_easting(self.zone, easting as i32),
+            row: RowLetter::from_zone_and_northing(self.zone, northing as i32),
+        }
+    }
+}
+
+fn multiples(from: f64, to: f64, step: f64) -> impl Iterator<Item = f64> {
+    // multiples of step within from..=to
+    let first = f64::ceil(from / step) as i64;
+    let last = f64::floor(to / step) as i64;
+    (first..last + 1).map(move |i| i as f64 * step)
+}
+
+fn along_edge(points: &[LatLon], rect: &[LatLon; 2]) -> bool {
+    let on = |v: f64, w: f64| (v - w).abs() < 1e-9;
+    points.iter().all(|p| on(p.lon, rect[0].lon)) || points.iter().all(|p| on(p.lon, rect[1].lon)) ||
+    points.iter().all(|p| on(p.lat, rect[0].lat)) || points.iter().all(|p| on(p.lat, rect[1].lat))
+}
+
+fn midpoint(points: &[LatLon]) -> LatLon {
+    /*!
+    Returns the point half way along a line, measured in degrees.
+    */
+
+    let length = |a: &LatLon, b: &LatLon| f64::hypot(b.lat - a.lat, b.lon - a.lon);
+    let total: f64 = points.windows(2).map(|p| length(&p[0], &p[1])).sum();
+    let mut remaining = total / 2.0;
+    for p in points.windows(2) {
+        let l = length(&p[0], &p[1]);
+        if l >= remaining && l > 0.0 {
+            let t = remaining / l;
+            return LatLon { lat: p[0].lat + t * (p[1].lat - p[0].lat), lon: p[0].lon + t * (p[1].lon - p[0].lon), ..p[0] };
+        }
+        remaining -= l;
+    }
+    points[0]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{for_viewport, GridLineKind, MAX_LINES};
+    use Accuracy;
+    use Errors;
+    use latlon::LatLon;
+
+    #[test]
+    fn gzd_and_square_labels() {
+        let viewport = [LatLon { lat: 47.5, lon: 5.5, ..Default::default() },
+                        LatLon { lat: 48.5, lon: 6.5, ..Default::default() }];
+        let lines = for_viewport(&viewport, Accuracy::TenThousand).unwrap();
+        let labels = |kind| -> Vec<String> {
+            lines.iter().filter(|l| l.kind == kind).flat_map(|l| l.labels.iter().map(|label| label.text.clone())).collect()
+        };
+        assert_eq!(labels(GridLineKind::Zone), vec!["32T", "32U"]);
+        assert_eq!(labels(GridLineKind::Band), vec!["31U", "32U"]);
+        assert!(!labels(GridLineKind::Square100k).is_empty());
+        assert!(lines.iter().all(|l| l.points.len() > 1 && l.labels.len() == 1));
+    }
+
+    #[test]
+    fn grid_digits() {
+        let viewport = [LatLon::new(48.84, 2.28).unwrap(), LatLon::new(48.86, 2.30).unwrap()];
+        let lines = for_viewport(&viewport, Accuracy::OneThousand).unwrap();
+        let grid: Vec<&str> = lines.iter().filter(|l| l.kind == GridLineKind::Grid).map(|l| &*l.labels[0].text).collect();
+        assert!(!grid.is_empty());
+        assert!(grid.iter().all(|t| t.len() == 2));
+    }
+
+    #[test]
+    fn too_many_lines() {
+        let viewport = [LatLon::new(45.0, 0.0).unwrap(), LatLon::new(50.0, 10.0).unwrap()];
+        match for_viewport(&viewport, Accuracy::Ten) {
+            Err(Errors::TooManyGridLines(n)) => assert!(n > MAX_LINES),
+            r => panic!("expected TooManyGridLines, got {} lines", r.map(|l| l.len()).unwrap_or(0)),
+        }
+        assert!(for_viewport(&viewport, Accuracy::TenThousand).is_ok());
+    }
+}
@@ -1,13 +1,27 @@
+use std::fmt;
+
 use ascii;
 use col::ColLetter;
 use row::RowLetter;
 use band::LatBand;
+use latlon::LatLon;
+use datum::Datum;
+use zone_policy::ZonePolicy;
+use covering::{zone_projection, grid_extent};
+use polygon;
+use Errors;
+use Accuracy;
+use Mgrs;
+use get_100k_set_for_zone;
 use SET_ORIGIN_ROW_LETTERS;
 use SET_ORIGIN_COLUMN_LETTERS;
 
+// latitude band letters from 80°S, 8° apart
+const BANDS: &[u8; 20] = b"CDEFGHJKLMNPQRSTUVWX";
+
 /// Grid Zone Designator such as 28F comprised of a Zone Number (one or two digits) and Zone Letter
 /// (C-X, minus I and O)
-#[derive(Default, Copy, Clone, Debug)]
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
 pub struct Gzd {
     /// 6° longitudinal zone (1..60 covering 180°W..180°E)
     pub zone: u8,
@@ -15,6 +29,165 @@ pub struct Gzd {
     pub band: LatBand,
 }
 
+impl Gzd {
+    pub fn from_latlon(ll: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Returns the GZD containing a latitude/longitude, taking the Norway and Svalbard exceptions
+        into account.
+
+        ### Returns
+         * **Ok**: The GZD.
+         * **Err**: `Errors::InvalidLatitude` if the point lies outside of the UTM latitude range
+           of 80°S..84°N.
+
+        # Examples
+
+        ```
+        let gzd = Gzd::from_latlon(&LatLon::new(60.0, 4.0).unwrap()).unwrap(); // gzd.to_string() = '32V'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        if !(-80.0 <= ll.lat && ll.lat <= 84.0) { return Err(Errors::InvalidLatitude(ll.lat)); }
+
+        // X is 12° tall, so 84°N falls within it
+        let band = usize::min(f64::floor((ll.lat + 80.0) / 8.0) as usize, BANDS.len() - 1);
+        Ok(Gzd {
+            zone: ZonePolicy::Standard.zone_for(&ll)?,
+            band: LatBand::from(BANDS[band]),
+        })
+    }
+
+    pub fn all() -> impl Iterator<Item = Gzd> {
+        /*!
+        Returns every valid GZD, band by band from 1C to 60X, leaving out 32X, 34X and 36X.
+        */
+
+        (0..BANDS.len())
+            .flat_map(|band| (1..61).map(move |zone| Gzd { zone, band: LatBand::from(BANDS[band]) }))
+            .filter(|gzd| gzd.bounds().is_ok())
+    }
+
+    pub fn bounds(&self) -> Result<[LatLon; 2], Errors> {
+        /*!
+        Returns the extent of this GZD, taking the Norway and Svalbard exceptions into account:
+        32V is widened west to 3°E at the expense of 31V, and 31X, 33X, 35X and 37X are widened
+        to cover 32X, 34X and 36X, which do not exist.
+
+        ### Returns
+         * **Ok**: The bottom-left and top-right of the GZD.
+         * **Err**: `Errors::InvalidZone` if the zone is not 1..60, or is one of the zones absent
+           from band X.
+
+        # Examples
+
+        ```
+        let gzd = Gzd { zone: 32, band: LatBand::V };
+        let rect = gzd.bounds().unwrap(); // rect = [56°N 3°E, 64°N 12°E]
+        ```
+        */
+
+        if !(1 <= self.zone && self.zone <= 60) { return Err(Errors::InvalidZone(self.zone)); }
+
+        let south = -80.0 + 8.0 * self.band.index() as f64;
+        let north = if self.band == LatBand::X { 84.0 } else { south + 8.0 };
+        let mut west = (self.zone as i32 - 1) as f64 * 6.0 - 180.0;
+        let mut east = west + 6.0;
+        match (self.band, self.zone) {
+            (LatBand::V, 31) => east = 3.0,
+            (LatBand::V, 32) => west = 3.0,
+            (LatBand::X, 32) | (LatBand::X, 34) | (LatBand::X, 36) => return Err(Errors::InvalidZone(self.zone)),
+            (LatBand::X, 31) => east = 9.0,
+            (LatBand::X, 33) | (LatBand::X, 35) => { west -= 3.0; east += 3.0; },
+            (LatBand::X, 37) => west = 33.0,
+            _ => {},
+        }
+        Ok([LatLon { lat: south, lon: west, ..Default::default() },
+            LatLon { lat: north, lon: east, ..Default::default() }])
+    }
+
+    pub fn contains(&self, ll: &LatLon) -> bool {
+        /*!
+        Returns whether a latitude/longitude falls within this GZD. Points on a boundary belong to
+        the GZD to their north and east, except at 84°N and 180°E.
+        */
+
+        match Gzd::from_latlon(ll) {
+            Ok(gzd) => gzd == *self,
+            Err(_) => false,
+        }
+    }
+
+    pub fn children(&self) -> Result<Vec<Mgrs>, Errors> {
+        /*!
+        Returns the 100km squares overlapping this GZD, as grid references of
+        `Accuracy::OneHundredThousand`, the level below the GZD in `Mgrs::parent`.
+
+        ### Returns
+         * **Ok**: The squares, row by row from the south-west.
+         * **Err**: `Errors::InvalidZone` if the GZD does not exist.
+
+        # Examples
+
+        ```
+        let gzd = Gzd { zone: 31, band: LatBand::U };
+        let squares = gzd.children().unwrap(); // squares.len() = 54
+        ```
+        */
+
+        Ok(GridSquareId100k::in_gzd(self)?.iter()
+            .map(|s| Mgrs { gzd: s.gzd, gsid_100k: s.id, accuracy: Accuracy::OneHundredThousand, ..Default::default() })
+            .collect())
+    }
+
+    pub fn contains_mgrs(&self, mgrs: &Mgrs) -> bool {
+        /*!
+        Returns whether a grid reference's cell lies within this GZD, such as `31U` containing
+        `31U DQ 48 11`.
+        */
+
+        let ids = self.cell_ids();
+        ids.start <= mgrs.cell_id() && mgrs.cell_id() < ids.end
+    }
+
+    pub fn cell_id(&self) -> u64 {
+        /*!
+        Returns the cell ID of this GZD, which sorts before the IDs of every grid reference within
+        it, see `Mgrs::cell_id`.
+        */
+
+        (self.zone as u64) << 52 | (self.band.index() as u64) << 47
+    }
+
+    pub fn cell_ids(&self) -> ::std::ops::Range<u64> {
+        /*!
+        Returns the range of cell IDs of this GZD and every grid reference within it, see
+        `Mgrs::cell_ids`.
+        */
+
+        let id = self.cell_id();
+        id..(id + (1 << 47))
+    }
+
+    pub fn from_cell_id(id: u64) -> Result<Self, Errors> {
+        /*!
+        Converts a cell ID from `Gzd::cell_id` back to a GZD.
+
+        ### Returns
+         * **Ok**: The GZD.
+         * **Err**: `Errors::InvalidGridReference` if the ID is not that of a GZD, including the
+           ID of a grid reference within one (see `Mgrs::from_cell_id`).
+        */
+
+        let invalid = || Errors::InvalidGridReference(id.to_string());
+        // range check the zone bits before narrowing them
+        let zone = id >> 52;
+        if !(1..=60).contains(&zone) || id & ((1 << 47) - 1) != 0 { return Err(invalid()); }
+        let band = LatBand::from_index((id >> 47 & 0x1f) as usize).ok_or_else(invalid)?;
+        Ok(Gzd { zone: zone as u8, band })
+    }
+}
+
 /// 100k Grid Square ID such as FD
 #[derive(Default, Copy, Clone, Debug)]
 pub struct GridSquareId100k {
@@ -22,7 +195,79 @@ pub struct GridSquareId100k {
     pub row: RowLetter
 }
 
+/// 100km grid square within a GZD, see `GridSquareId100k::in_gzd`
+#[derive(Clone, Debug)]
+pub struct Square100k {
+    pub gzd: Gzd,
+    pub id: GridSquareId100k,
+    /// Easting of the south-west corner of the square on the zone's grid, in metres
+    pub easting: u32,
+    /// Northing of the south-west corner of the square on the zone's grid, in metres
+    pub northing: u32,
+    /// Outline of the part of the square within the GZD, anticlockwise
+    pub polygon: Vec<LatLon>,
+    /// Whether the square is cut by a zone or band boundary of the GZD
+    pub partial: bool,
+}
+
 impl GridSquareId100k {
+    pub fn in_gzd(gzd: &Gzd) -> Result<Vec<Square100k>, Errors> {
+        /*!
+        Lists the 100km squares overlapping a GZD.
+
+        ### Params
+         * **gzd**: The GZD, which must be valid (see `Gzd::bounds`).
+
+        ### Returns
+         * **Ok**: The squares, row by row from the south-west, each clipped to the GZD.
+         * **Err**: `Errors::InvalidZone` if the GZD does not exist.
+
+        # Examples
+
+        ```
+        let squares = GridSquareId100k::in_gzd(&Gzd { zone: 31, band: LatBand::U }).unwrap();
+        let dq = squares.iter().find(|s| s.id.to_string() == "DQ").unwrap(); // dq.partial = false
+        ```
+        */
+
+        let rect = gzd.bounds()?;
+        let corners = [rect[0], LatLon { lon: rect[1].lon, ..rect[0] },
+                       rect[1], LatLon { lon: rect[0].lon, ..rect[1] }];
+        let tm = zone_projection(gzd);
+        let (min_e, min_n, max_e, max_n) = grid_extent(&tm, &corners);
+
+        let size = 100000;
+        let steps = 10;
+        let set = get_100k_set_for_zone(gzd.zone as usize);
+        let mut squares = Vec::new();
+        for row in (min_n as u32 / size)..(max_n as u32 / size + 1) {
+            for col in (min_e as u32 / size)..(max_e as u32 / size + 1) {
+                let (easting, northing) = (col * size, row * size);
+                let mut edge = Vec::with_capacity(4 * steps as usize);
+                for i in 0..steps { edge.push((size * i / steps, 0)); }
+                for i in 0..steps { edge.push((size, size * i / steps)); }
+                for i in 0..steps { edge.push((size - size * i / steps, size)); }
+                for i in 0..steps { edge.push((0, size - size * i / steps)); }
+                let outline: Vec<LatLon> = edge.iter()
+                    .map(|&(de, dn)| tm.inverse((easting + de) as f64, (northing + dn) as f64))
+                    .collect();
+
+                let clipped = polygon::clip_to_rect(&outline, &rect);
+                let area = polygon::area(&clipped);
+                if area <= 0.0 { continue; }
+                squares.push(Square100k {
+                    gzd: *gzd,
+                    id: GridSquareId100k::new(col, row % 20, set),
+                    easting,
+                    northing,
+                    partial: area < polygon::area(&outline) * (1.0 - 1e-9),
+                    polygon: clipped,
+                });
+            }
+        }
+        Ok(squares)
+    }
+
     pub fn new(column: u32, row: u32, parm: usize) -> GridSquareId100k {
         /*!
         Get the two-letter MGRS 100k designator given information translated from the UTM northing,
@@ -30,11 +275,11 @@ impl GridSquareId100k {
 
         ### Params
          * **column**: the column index as it relates to the MGRS 100k set spreadsheet, created from
-                       the UTM easting. Values are 1-8.
+           the UTM easting. Values are 1-8.
          * **row**: the row index as it relates to the MGRS 100k set spreadsheet, created from the UTM
-                    northing value. Values are from 0-19.
+           northing value. Values are from 0-19.
          * **parm**: the set block, as it relates to the MGRS 100k set spreadsheet, created from the UTM
-                    zone. Values are from 1-60.
+           zone. Values are from 1-60.
         ### Return
          * two letter MGRS 100k code as a `GridSquareId100k`.
         */
@@ -99,3 +344,54 @@ impl GridSquareId100k {
         }
     }
 }
+
+impl fmt::Display for Gzd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}{}", self.zone, self.band)
+    }
+}
+
+impl fmt::Display for GridSquareId100k {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.col, self.row)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exceptions() {
+        assert_eq!(Gzd::all().count(), 60 * 20 - 3);
+        assert!(Gzd::all().all(|gzd| {
+            let rect = gzd.bounds().unwrap();
+            let centre = LatLon { lat: (rect[0].lat + rect[1].lat) / 2.0, lon: (rect[0].lon + rect[1].lon) / 2.0, ..Default::default() };
+            gzd.contains(&centre)
+        }));
+
+        let at = |lat, lon| Gzd::from_latlon(&LatLon { lat, lon, ..Default::default() }).unwrap().to_string();
+        assert_eq!(&*at(60.0, 2.9), "31V");
+        assert_eq!(&*at(60.0, 3.0), "32V");
+        assert_eq!(&*at(78.0, 8.9), "31X");
+        assert_eq!(&*at(78.0, 9.0), "33X");
+        assert_eq!(&*at(84.0, 40.0), "37X");
+        assert_eq!(&*at(-0.5, 0.0), "31M");
+        assert!(Gzd { zone: 34, band: LatBand::X }.bounds().is_err());
+        assert!(Gzd::from_latlon(&LatLon { lat: 84.5, lon: 0.0, ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn squares() {
+        let gzd = Gzd { zone: 31, band: LatBand::U };
+        let squares = GridSquareId100k::in_gzd(&gzd).unwrap();
+        assert_eq!(squares.len(), 54);
+        let dq = squares.iter().find(|s| s.id.to_string() == "DQ").unwrap();
+        assert!(!dq.partial);
+        assert_eq!((dq.easting, dq.northing), (400000, 5400000));
+        // 31V stops at the central meridian, where 32V takes over
+        let squares = GridSquareId100k::in_gzd(&Gzd { zone: 31, band: LatBand::V }).unwrap();
+        assert!(squares.iter().all(|s| s.easting < 500000));
+        assert!(GridSquareId100k::in_gzd(&Gzd { zone: 32, band: LatBand::X }).is_err());
+    }
+}
@@ -4,8 +4,9 @@ use std::fmt;
 use Errors;
 use band::LatBand;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub enum Hemisphere {
+    #[default]
     N,
     S,
 }
@@ -14,12 +15,6 @@ pub enum Hemisphere {
 ///////////// impls ///////////////
 ///////////////////////////////////
 
-impl Default for Hemisphere {
-    fn default() -> Self {
-        Hemisphere::N
-    }
-}
-
 impl From<char> for Hemisphere {
     fn from(c: char) -> Self {
         match c {
@@ -54,7 +49,7 @@ impl From<u8> for Hemisphere {
 
 impl<'s> From<&'s str> for Hemisphere {
     fn from(s: &'s str) -> Self {
-        Hemisphere::from(s.as_ref())
+        s.parse().unwrap_or_else(|_| panic!("Invalid hemisphere character {}", s))
     }
 }
 
@@ -96,6 +91,6 @@ impl From<Hemisphere> for char {
 impl fmt::Display for Hemisphere {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let c: char = (*self).into();
-        writeln!(f, "{}", c)
+        write!(f, "{}", c)
     }
 }
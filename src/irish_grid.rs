@@ -0,0 +1,341 @@
+use std::str::FromStr;
+use std::fmt;
+
+use ascii;
+use Accuracy;
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use ellipsoid::Ellipsoid;
+use parser::{IrishGridParser, ItmParser};
+use tm::TransverseMercator;
+
+// extent of the grid from its false origin (grid square V), in metres
+const MAX_EASTING: f64 = 500e3;
+const MAX_NORTHING: f64 = 500e3;
+
+/// Irish Grid reference on the TM75 datum, such as `O 15904 34671`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct IrishGrid {
+    /// Easting in metres from the false origin
+    pub easting: f64,
+    /// Northing in metres from the false origin
+    pub northing: f64,
+    /// Precision the grid reference was given at
+    pub accuracy: Accuracy,
+}
+
+impl IrishGrid {
+    pub fn new(easting: f64, northing: f64) -> Result<Self, Errors> {
+        /*!
+        Creates an Irish Grid reference from a numeric easting/northing.
+
+        ### Params
+         * **easting**: Easting in metres from the false origin.
+         * **northing**: Northing in metres from the false origin.
+
+        ### Returns
+         * **Ok**: The grid reference.
+         * **Err**: `Errors::OutsideGrid` if the easting/northing falls outside of the grid.
+        */
+
+        if !(0.0..MAX_EASTING).contains(&easting) || !(0.0..MAX_NORTHING).contains(&northing) {
+            return Err(Errors::OutsideGrid);
+        }
+        Ok(IrishGrid {
+            easting,
+            northing,
+            accuracy: Accuracy::One,
+        })
+    }
+
+    pub fn projection() -> TransverseMercator {
+        /*!
+        Returns the Transverse Mercator projection the Irish Grid is defined by.
+        */
+
+        TransverseMercator {
+            lon0: -8.0,
+            lat0: 53.5,
+            k0: 1.000035,
+            false_easting: 200e3,
+            false_northing: 250e3,
+            ellipsoid: Ellipsoid::AiryModified,
+        }
+    }
+
+    pub fn from_ll(ll: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to an Irish Grid reference, shifting the point onto the TM75
+        datum first if required.
+
+        ### Returns
+         * **Ok**: The grid reference.
+         * **Err**: `Errors::OutsideGrid` if the point falls outside of the grid.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(53.349804, -6.260310).unwrap();
+        let irish = IrishGrid::from_ll(&ll).unwrap(); // irish.to_string() = 'O 15899 34671'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Tm75);
+        let p = IrishGrid::projection().forward(&ll);
+        IrishGrid::new(p.easting, p.northing)
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this grid reference to WGS84 latitude/longitude.
+
+        Use `LatLon::to_datum(Datum::Tm75)` on the result for TM75 latitude/longitude.
+        */
+
+        let mut ll = IrishGrid::projection().inverse(self.easting, self.northing);
+        ll.datum = Datum::Tm75;
+        ll.to_datum(Datum::Wgs84)
+    }
+
+    pub fn letter(&self) -> char {
+        /*!
+        Returns the 100km grid square letter this reference falls within, such as `'O'`.
+        */
+
+        let e100k = f64::floor(self.easting / 100000.0) as u8;
+        let n100k = f64::floor(self.northing / 100000.0) as u8;
+
+        // letters run A-Z (skipping 'I') in rows of 5, west to east, from the north-west corner
+        let mut l = (4 - n100k) * 5 + e100k;
+        if l > 7 { l += 1; }
+        (ascii::A + l) as char
+    }
+
+    fn as_string(&self, accuracy: Accuracy) -> String {
+        /*!
+        Returns a string representation of an Irish Grid reference.
+
+        Components are separated by spaces: for an unseparated string, use
+        `irish.as_string(Accuracy::One).replace(" ", "");`
+
+        ### Params
+         * **accuracy** Precision of returned grid reference (eg `One` = 1m or 10 digit grid,
+           `Ten` = 10m or 8 digit grid, etc.).
+
+        ### Returns
+         * This grid reference in standard format.
+        */
+
+        let digits: i32 = accuracy.as_num_digits() as i32 / 2;
+        // strip 100km-grid indices from easting & northing, and set required precision
+        let easting = (f64::floor((self.easting % 100000.0) / f64::powi(10.0, 5 - digits))) as usize;
+        let northing = (f64::floor((self.northing % 100000.0) / f64::powi(10.0, 5 - digits))) as usize;
+        if digits == 0 { return self.letter().to_string(); }
+
+        format!("{0} {1:0>3$} {2:0>3$}", self.letter(), easting, northing, digits as usize)
+    }
+}
+
+/// Irish Transverse Mercator coordinate on the IRENET95 datum
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Itm {
+    /// Easting in metres from the false origin
+    pub easting: f64,
+    /// Northing in metres from the false origin
+    pub northing: f64,
+}
+
+impl Itm {
+    pub fn new(easting: f64, northing: f64) -> Self {
+        Itm {
+            easting,
+            northing,
+        }
+    }
+
+    pub fn projection() -> TransverseMercator {
+        /*!
+        Returns the Transverse Mercator projection Irish Transverse Mercator is defined by.
+        */
+
+        TransverseMercator {
+            lon0: -8.0,
+            lat0: 53.5,
+            k0: 0.99982,
+            false_easting: 600e3,
+            false_northing: 750e3,
+            ellipsoid: Ellipsoid::Grs80,
+        }
+    }
+
+    pub fn from_ll(ll: &LatLon) -> Self {
+        /*!
+        Converts latitude/longitude to an ITM coordinate, shifting the point onto the IRENET95
+        datum first if required.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(53.349804, -6.260310).unwrap();
+        let itm = Itm::from_ll(&ll); // itm.to_string() = '715826 734698'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Irenet95);
+        let p = Itm::projection().forward(&ll);
+        Itm::new(p.easting, p.northing)
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this coordinate to WGS84 latitude/longitude.
+        */
+
+        let mut ll = Itm::projection().inverse(self.easting, self.northing);
+        ll.datum = Datum::Irenet95;
+        ll.to_datum(Datum::Wgs84)
+    }
+
+    fn as_string(&self, digits: usize) -> String {
+        /*!
+        Returns a string representation of an ITM coordinate as the easting and the northing.
+
+        ### Params
+         * **digits** Determines the number of digits to return after the decimal
+        */
+
+        format!("{0:.2$} {1:.2$}", self.easting, self.northing, digits)
+    }
+}
+
+impl From<IrishGrid> for LatLon {
+    fn from(irish: IrishGrid) -> Self {
+        irish.to_ll()
+    }
+}
+
+impl From<Itm> for LatLon {
+    fn from(itm: Itm) -> Self {
+        itm.to_ll()
+    }
+}
+
+impl FromStr for IrishGrid {
+    type Err = Errors;
+    /// Parses an Irish Grid reference, either as a letter and digits (`O 15904 34671`,
+    /// `O1590434671`), or as a fully numeric easting and northing (`315904,234671`).
+    fn from_str(irish: &str) -> Result<Self, Self::Err> {
+        IrishGridParser::new(irish.as_bytes()).parse()
+    }
+}
+
+impl FromStr for Itm {
+    type Err = Errors;
+    /// Parses an ITM easting and northing, separated by a comma and/or whitespace
+    /// (`715830 734697`, `715830,734697`).
+    fn from_str(itm: &str) -> Result<Self, Self::Err> {
+        ItmParser::new(itm.as_bytes()).parse()
+    }
+}
+
+impl fmt::Display for IrishGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(self.accuracy))
+    }
+}
+
+impl fmt::Display for Itm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{IrishGrid, Itm};
+    use Accuracy;
+    use latlon::LatLon;
+    use datum::Datum;
+
+    #[test]
+    fn false_origins() {
+        // OSi 'Making maps compatible with GPS': both grids are centred on 53°30'N 8°W
+        let origin = LatLon { lat: 53.5, lon: -8.0, datum: Datum::Tm75, ..Default::default() };
+        let p = IrishGrid::projection().forward(&origin);
+        assert!((p.easting - 200000.0).abs() < 1e-6);
+        assert!((p.northing - 250000.0).abs() < 1e-6);
+
+        let origin = LatLon { lat: 53.5, lon: -8.0, datum: Datum::Irenet95, ..Default::default() };
+        let p = Itm::projection().forward(&origin);
+        assert!((p.easting - 600000.0).abs() < 1e-6);
+        assert!((p.northing - 750000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let irish: IrishGrid = "O 15904 34671".parse().unwrap();
+        assert_eq!(irish.easting, 315904.0);
+        assert_eq!(irish.northing, 234671.0);
+        assert_eq!(irish.letter(), 'O');
+        assert_eq!(&*irish.to_string(), "O 15904 34671");
+
+        let irish: IrishGrid = "o1590434671".parse().unwrap();
+        assert_eq!(&*irish.to_string(), "O 15904 34671");
+
+        let irish: IrishGrid = "O 159 346".parse().unwrap();
+        assert_eq!(irish.accuracy, Accuracy::OneHundred);
+        assert_eq!(irish.easting, 315900.0);
+        assert_eq!(&*irish.to_string(), "O 159 346");
+
+        let irish: IrishGrid = "315904,234671".parse().unwrap();
+        assert_eq!(&*irish.to_string(), "O 15904 34671");
+
+        let irish: IrishGrid = "V 00000 00000".parse().unwrap();
+        assert_eq!(irish.easting, 0.0);
+        assert_eq!(irish.northing, 0.0);
+
+        assert!("I 123 456".parse::<IrishGrid>().is_err());
+        assert!("O 123 45".parse::<IrishGrid>().is_err());
+        assert!("600000,100000".parse::<IrishGrid>().is_err());
+
+        let itm: Itm = "715830 734697".parse().unwrap();
+        assert_eq!(itm.easting, 715830.0);
+        assert_eq!(itm.northing, 734697.0);
+        assert_eq!(&*itm.to_string(), "715830 734697");
+        let itm: Itm = "715830,734697".parse().unwrap();
+        assert_eq!(itm.northing, 734697.0);
+        assert!("715830".parse::<Itm>().is_err());
+    }
+
+    #[test]
+    fn spire_of_dublin() {
+        // the Spire is published at O 15904 34671 on the Irish Grid and 715830 734697 in ITM, so
+        // both must resolve to the same point to within the TM75 transformation's metre accuracy
+        let irish: IrishGrid = "O 15904 34671".parse().unwrap();
+        let itm: Itm = "715830 734697".parse().unwrap();
+        let a = irish.to_ll();
+        let b = itm.to_ll();
+        assert!((a.lat - b.lat).abs() < 1e-5);
+        assert!((a.lon - b.lon).abs() < 1e-5);
+        assert!((a.lat - 53.3498).abs() < 1e-4);
+        assert!((a.lon - -6.2603).abs() < 1e-4);
+    }
+
+    #[test]
+    fn round_trip_wgs84() {
+        let ll = LatLon::new(53.349804, -6.260310).unwrap();
+        let back = IrishGrid::from_ll(&ll).unwrap().to_ll();
+        assert!((back.lat - ll.lat).abs() < 1e-6);
+        assert!((back.lon - ll.lon).abs() < 1e-6);
+
+        let back = Itm::from_ll(&ll).to_ll();
+        assert!((back.lat - ll.lat).abs() < 1e-6);
+        assert!((back.lon - ll.lon).abs() < 1e-6);
+
+        let itm = Itm::from_ll(&ll);
+        let again = Itm::from_ll(&itm.to_ll());
+        assert!((again.easting - itm.easting).abs() < 1e-3);
+        assert!((again.northing - itm.northing).abs() < 1e-3);
+    }
+}
@@ -1,13 +1,7 @@
-use Lat;
-use Lon;
 use Utm;
 use Mgrs;
-use Accuracy;
-use Gzd;
-use band::LatBand;
 use errors::Errors;
-use hemisphere::Hemisphere;
-use datum::Datum;
+use datum::{self, Datum};
 
 #[derive(Copy, Clone, Debug, Default)]
 pub struct LatLon {
@@ -23,12 +17,12 @@ pub struct LatLon {
 
 impl LatLon {
     pub fn new(lat: f64, lon: f64) -> Result<Self, Errors> {
-        if (!(-80.0 <= lat && lat <= 84.0)) {
+        if !(-80.0..=84.0).contains(&lat) {
             return Err(Errors::InvalidLatitude(lat));
         }
         Ok(LatLon {
-            lat: lat,
-            lon: lon,
+            lat,
+            lon,
             datum: Datum::Wgs84,
             scale: None,
             convergence: None,
@@ -129,6 +123,47 @@ impl LatLon {
         m.to_ll()
     }
 
+    pub fn to_datum(&self, datum: Datum) -> Self {
+        /*!
+        Converts this point to another datum, via a Helmert transform through WGS84.
+
+        ### Params
+         * **datum**: The datum to convert to.
+
+        ### Return
+         * This point's latitude/longitude on `datum`.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(55.7539, 37.6208).unwrap();
+        let sk42 = ll.to_datum(Datum::Pulkovo1942);
+        ```
+        */
+
+        if self.datum == datum { return *self; }
+
+        let mut p = datum::to_cartesian(self.lat, self.lon, self.datum.ellipsoid());
+
+        // transform into WGS84 by inverting this datum's transform
+        if self.datum != Datum::Wgs84 {
+            let t = self.datum.transform();
+            p = datum::apply_transform(p, [-t[0], -t[1], -t[2], -t[3], -t[4], -t[5], -t[6]]);
+        }
+        if datum != Datum::Wgs84 {
+            p = datum::apply_transform(p, datum.transform());
+        }
+
+        let (lat, lon) = datum::from_cartesian(p, datum.ellipsoid());
+        LatLon {
+            lat,
+            lon,
+            datum,
+            convergence: None,
+            scale: None,
+        }
+    }
+
     // pub fn rect_from_mgrs<M: Into<Mgrs>>(m: M) -> Option<[LatLon; 2]> {
     //     let mgrs = m.into();
     //     let bl = LatLon::from_utm(&mgrs.utm).expect("failed to convert MGRS to Lat/Lon");
@@ -182,98 +217,7 @@ impl From<Utm> for LatLon {
           let grid = new Utm(31, 'N', 448251.795, 5411932.678);
           let latlong = grid.toLatLonE(); // latlong.toString(): 48°51′29.52″N, 002°17′40.20″E
         */
-        let z = utm.zone;
-        let h = utm.hemisphere;
-        let x = utm.easting;
-        let y = utm.northing;
-
-        let false_easting = 500_000;
-        let false_northing = 10_000_000;
-
-        // WGS 84:  a = 6378137, b = 6356752.314245, f = 1/298.257223563;
-        let a = utm.datum.a();
-        let f = utm.datum.f();
-
-        let k0 = 0.9996; // UTM scale on the central meridian
-
-        x = x - false_easting;               // make x ± relative to central meridian
-        y = if h == Hemisphere::S { y - false_northing } else { y }; // make y ± relative to equator
-
-        // ---- from Karney 2011 Eq 15-22, 36:
-
-        let e = f64::sqrt(f * (2.0 - f)); // eccentricity
-        let n = f / (2.0 - f);        // 3rd flattening
-        let n2 = n * n;
-        let n3 = n * n2;
-        let n4 = n * n3;
-        let n5 = n * n4;
-        let n6 = n * n5;
-
-        let A = a / (1.0 + n) * (1.0 + 1.0 / 4.0 * n2 + 1.0 / 64.0 * n4 + 1.0 / 256.0 * n6); // 2πA is the circumference of a meridian
-
-        let eta = x as f64 / (k0*A);
-        let xi = y as f64 / (k0*A);
-
-        let beta = [ 0.0, // note beta is one-based array (6th order Krüger expressions)
-            1.0 / 2.0 * n - 2.0 / 3.0 * n2 + 37.0 / 96.0 * n3 - 1.0 / 360.0 * n4 - 81.0 / 512.0 * n5 + 96199.0 / 604800.0 * n6,
-            1.0 / 48.0 * n2 + 1.0 / 15.0 * n3 - 437.0 / 1440.0 * n4 + 46.0 / 105.0 * n5 - 1118711.0 / 3870720.0 * n6,
-            17.0 / 480.0 * n3 - 37.0 / 840.0 * n4 - 209.0 / 4480.0 * n5 + 5569.0 / 90720.0 * n6,
-            4397.0 / 161280.0 * n4 - 11.0 / 504.0 * n5 - 830251.0 / 7257600.0 * n6,
-            4583.0 / 161280.0 * n5 - 108847.0 / 3991680.0 * n6,
-            20648693.0 / 638668800.0 * n6 ];
-
-        let mut xi2 = xi;
-        for j in 1..6 { xi2 -= beta[j] * f64::sin(2.0 * j as f64 * xi) * f64::cosh(2.0 * j as f64 * eta); }
-
-        let mut eta2 = eta;
-        for j in 1..6 { eta2 -= beta[j] * f64::cos(2.0 * j as f64* xi) * f64::sinh(2.0 * j as f64 * eta); }
-
-        let sinheta2 = f64::sinh(eta2);
-        let sinxi2 = f64::sin(xi2);
-        let cosxi2 = f64::cos(xi2);
-
-        let tau2 = sinxi2 / f64::sqrt(sinheta2 * sinheta2 + cosxi2 * cosxi2);
-
-        let taui = tau2;
-        loop {
-            let sigmai = f64::sinh(e * f64::atanh(e * taui / f64::sqrt(1.0 + taui * taui)));
-            let taui2 = taui * f64::sqrt(1.0 + sigmai * sigmai) - sigmai * f64::sqrt(1.0 + taui * taui);
-            let deltataui = (tau2 - taui2) / f64::sqrt(1.0 + taui2 * taui2)
-                * (1.0 + (1.0 - e * e) * taui * taui) / ((1.0 - e * e) * f64::sqrt(1.0 + taui * taui));
-             taui += deltataui;
-            if !(f64::abs(deltataui) > 1e-12) { break; } // using IEEE 754 deltataui -> 0 after 2-3 iterations
-        }
-        // note relatively large convergence test as deltataui toggles on ±1.12e-16 for eg 31 N 400000 5000000
-        let tau = taui;
-
-        let phi = f64::atan(tau);
-
-        let lamda = f64::atan2(sinheta2, cosxi2);
-
-        // ---- convergence: Karney 2011 Eq 26, 27
-
-        let mut p = 1.0;
-        for j in 1..6 { p -= 2.0 * j as f64 * beta[j] * f64::cos(2.0 * j as f64 * xi) * f64::cosh(2.0 * j as f64 * eta); }
-        let mut q = 0.0;
-        for j in 1..6 { q += 2.0 * j as f64 * beta[j] * f64::sin(2.0 * j as f64 * xi) * f64::sinh(2.0 * j as f64 * eta); }
-
-        let gamma2 = f64::atan(f64::tan(xi2) * f64::tanh(eta2));
-        let gamma3 = f64::atan2(q, p);
-
-        let gamma = gamma2 + gamma3;
-
-        // ---- scale: Karney 2011 Eq 28
-
-        let sinphi = f64::sin(phi);
-        let k2 = f64::sqrt(1.0 - e * e * sinphi * sinphi) * f64::sqrt(1.0 + tau * tau) * f64::sqrt(sinheta2 *sinheta2 + cosxi2 * cosxi2);
-        let k3 = A / a / f64::sqrt(p * p + q * q);
-
-        let k = k0 * k2 * k3;
-
-        // ------------
-
-        let lamda0: f64 = f64::to_radians(((z - 1) * 6 - 180 + 3) as f64); // longitude of central meridian
-        lamda += lamda0; // move lamda from zonal to global coordinates
+        let ll = utm.projection().inverse(utm.easting as f64, utm.northing as f64);
 
         // round to reasonable precision
         let to_precisionf = |x: f64, y: usize| -> f64 {
@@ -282,14 +226,14 @@ impl From<Utm> for LatLon {
         };
 
         // round to reasonable precision
-        let lat = to_precisionf(phi.to_degrees(), 11); // nm precision (1nm = 10^-11°)
-        let lon = to_precisionf(lamda.to_degrees(), 11); // (strictly lat rounding should be phi⋅cosphi!)
-        let convergence = to_precisionf(gamma.to_degrees(), 9);
-        let scale = to_precisionf(k, 12);
+        let lat = to_precisionf(ll.lat, 11); // nm precision (1nm = 10^-11°)
+        let lon = to_precisionf(ll.lon, 11); // (strictly lat rounding should be phi⋅cosphi!)
+        let convergence = to_precisionf(ll.convergence.unwrap_or(0.0), 9);
+        let scale = to_precisionf(ll.scale.unwrap_or(1.0), 12);
 
         LatLon {
-            lat: lat,
-            lon: lon,
+            lat,
+            lon,
             datum: utm.datum,
             convergence: Some(convergence),
             scale: Some(scale),
@@ -0,0 +1,268 @@
+use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::FRAC_PI_4;
+
+use Lat;
+use Lon;
+use latlon::LatLon;
+use ellipsoid::Ellipsoid;
+
+/// Lambert Conformal Conic projection with arbitrary parameters
+///
+/// Implements the EPSG formulation (Guidance Note 7-2) for both the one standard parallel (1SP)
+/// and two standard parallel (2SP) variants; a 1SP projection is one whose standard parallels are
+/// both the latitude of origin.
+#[derive(Copy, Clone, Debug)]
+pub struct LambertConformalConic {
+    /// Longitude of the central meridian, in degrees
+    pub lon0: Lon,
+    /// Latitude of the origin, in degrees
+    pub lat0: Lat,
+    /// Latitude of the first standard parallel, in degrees
+    pub lat1: Lat,
+    /// Latitude of the second standard parallel, in degrees
+    pub lat2: Lat,
+    /// Scale factor on the standard parallel(s)
+    pub k0: f64,
+    /// Easting of the origin, in metres
+    pub false_easting: f64,
+    /// Northing of the origin, in metres
+    pub false_northing: f64,
+    /// Ellipsoid the projection is defined on
+    pub ellipsoid: Ellipsoid,
+}
+
+/// A point projected onto a Lambert Conformal Conic grid
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LccCoord {
+    /// Easting in metres, including the false easting
+    pub easting: f64,
+    /// Northing in metres, including the false northing
+    pub northing: f64,
+    /// Meridian convergence (bearing of grid north clockwise from true north), in degrees
+    pub convergence: f64,
+    /// Grid scale factor
+    pub scale: f64,
+}
+
+impl LambertConformalConic {
+    pub fn one_sp(lon0: Lon, lat0: Lat, k0: f64, false_easting: f64, false_northing: f64, ellipsoid: Ellipsoid) -> Self {
+        /*!
+        Creates a projection with a single standard parallel at the latitude of origin (EPSG
+        method 9801).
+
+        ### Params
+         * **lon0**: Longitude of the central meridian, in degrees.
+         * **lat0**: Latitude of the origin and standard parallel, in degrees.
+         * **k0**: Scale factor on the standard parallel.
+         * **false_easting**: Easting of the origin, in metres.
+         * **false_northing**: Northing of the origin, in metres.
+         * **ellipsoid**: Ellipsoid the projection is defined on.
+        */
+
+        LambertConformalConic {
+            lon0,
+            lat0,
+            lat1: lat0,
+            lat2: lat0,
+            k0,
+            false_easting,
+            false_northing,
+            ellipsoid,
+        }
+    }
+
+    pub fn two_sp(lon0: Lon, lat0: Lat, lat1: Lat, lat2: Lat, false_easting: f64, false_northing: f64, ellipsoid: Ellipsoid) -> Self {
+        /*!
+        Creates a projection with two standard parallels, which have unit scale (EPSG method
+        9802).
+
+        ### Params
+         * **lon0**: Longitude of the central meridian, in degrees.
+         * **lat0**: Latitude of the false origin, in degrees.
+         * **lat1**: Latitude of the first standard parallel, in degrees.
+         * **lat2**: Latitude of the second standard parallel, in degrees.
+         * **false_easting**: Easting of the false origin, in metres.
+         * **false_northing**: Northing of the false origin, in metres.
+         * **ellipsoid**: Ellipsoid the projection is defined on.
+        */
+
+        LambertConformalConic {
+            lon0,
+            lat0,
+            lat1,
+            lat2,
+            k0: 1.0,
+            false_easting,
+            false_northing,
+            ellipsoid,
+        }
+    }
+
+    pub fn forward(&self, ll: &LatLon) -> LccCoord {
+        /*!
+        Projects latitude/longitude onto this grid.
+
+        ### Params
+         * **ll**: The point to project, on this projection's ellipsoid.
+
+        ### Returns
+         * The `LccCoord` easting, northing, convergence and scale of the point.
+        */
+
+        let (n, af, r0) = self.cone();
+        let phi = ll.lat.to_radians();
+        let r = af * f64::powf(self.t(phi), n);
+        // zones such as SPCS Alaska 10 reach across the antimeridian
+        let dlon = (ll.lon - self.lon0 + 540.0) % 360.0 - 180.0;
+        let theta = n * dlon.to_radians();
+
+        LccCoord {
+            easting: self.false_easting + r * theta.sin(),
+            northing: self.false_northing + r0 - r * theta.cos(),
+            convergence: theta.to_degrees(),
+            scale: r * n / (self.ellipsoid.a() * self.m(phi)),
+        }
+    }
+
+    pub fn inverse(&self, easting: f64, northing: f64) -> LatLon {
+        /*!
+        Converts a grid easting/northing back to latitude/longitude.
+
+        ### Params
+         * **easting**: Easting in metres, including the false easting.
+         * **northing**: Northing in metres, including the false northing.
+
+        ### Returns
+         * The `LatLon` of the point, with the convergence and scale at that point.
+        */
+
+        let (n, af, r0) = self.cone();
+        let e = self.e();
+        let x = easting - self.false_easting;
+        let y = r0 - (northing - self.false_northing);
+
+        let r = n.signum() * f64::sqrt(x * x + y * y);
+        let t = f64::powf(r / af, 1.0 / n);
+        let theta = if n > 0.0 { f64::atan2(x, y) } else { f64::atan2(-x, -y) };
+
+        // latitude by iteration of the conformal latitude
+        let mut phi = FRAC_PI_2 - 2.0 * t.atan();
+        loop {
+            let esinphi = e * phi.sin();
+            let phi2 = FRAC_PI_2 - 2.0 * f64::atan(t * f64::powf((1.0 - esinphi) / (1.0 + esinphi), e / 2.0));
+            let delta = phi2 - phi;
+            phi = phi2;
+            if delta.abs() <= 1e-12 || delta.is_nan() { break; }
+        }
+
+        LatLon {
+            lat: phi.to_degrees(),
+            lon: (self.lon0 + (theta / n).to_degrees() + 540.0) % 360.0 - 180.0,
+            convergence: Some(theta.to_degrees()),
+            scale: Some(r * n / (self.ellipsoid.a() * self.m(phi))),
+            ..Default::default()
+        }
+    }
+
+    fn cone(&self) -> (f64, f64, f64) {
+        /*!
+        Returns the constants of the cone: `(n, a·F·k0, r0)`, with `r0` the radius of the parallel
+        through the origin.
+        */
+
+        let phi0 = self.lat0.to_radians();
+        let phi1 = self.lat1.to_radians();
+        let phi2 = self.lat2.to_radians();
+        let (m1, t1) = (self.m(phi1), self.t(phi1));
+
+        let n = if (self.lat1 - self.lat2).abs() < 1e-12 {
+            phi1.sin()
+        } else {
+            (m1.ln() - self.m(phi2).ln()) / (t1.ln() - self.t(phi2).ln())
+        };
+        let af = self.ellipsoid.a() * self.k0 * m1 / (n * f64::powf(t1, n));
+        (n, af, af * f64::powf(self.t(phi0), n))
+    }
+
+    fn m(&self, phi: f64) -> f64 {
+        let esinphi = self.e() * phi.sin();
+        phi.cos() / f64::sqrt(1.0 - esinphi * esinphi)
+    }
+
+    fn t(&self, phi: f64) -> f64 {
+        let e = self.e();
+        let esinphi = e * phi.sin();
+        f64::tan(FRAC_PI_4 - phi / 2.0) / f64::powf((1.0 - esinphi) / (1.0 + esinphi), e / 2.0)
+    }
+
+    fn e(&self) -> f64 {
+        let f = self.ellipsoid.f();
+        f64::sqrt(f * (2.0 - f)) // eccentricity
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LambertConformalConic;
+    use ellipsoid::Ellipsoid;
+    use latlon::LatLon;
+
+    #[test]
+    fn unit_scale_on_standard_parallels() {
+        let lcc = LambertConformalConic::two_sp(-118.0, 33.5, 34.0 + 2.0 / 60.0, 35.0 + 28.0 / 60.0,
+                                                2000000.0, 500000.0, Ellipsoid::Grs80);
+        let origin = lcc.forward(&LatLon::new(33.5, -118.0).unwrap());
+        assert!((origin.easting - 2000000.0).abs() < 1e-6);
+        assert!((origin.northing - 500000.0).abs() < 1e-6);
+
+        assert!((lcc.forward(&LatLon::new(34.0 + 2.0 / 60.0, -117.0).unwrap()).scale - 1.0).abs() < 1e-12);
+        assert!((lcc.forward(&LatLon::new(35.0 + 28.0 / 60.0, -119.0).unwrap()).scale - 1.0).abs() < 1e-12);
+
+        let p = lcc.forward(&LatLon::new(34.1, -116.5).unwrap());
+        let ll = lcc.inverse(p.easting, p.northing);
+        assert!((ll.lat - 34.1).abs() < 1e-10);
+        assert!((ll.lon - -116.5).abs() < 1e-10);
+        assert!((ll.convergence.unwrap() - p.convergence).abs() < 1e-10);
+    }
+
+    #[test]
+    fn one_standard_parallel() {
+        let lcc = LambertConformalConic::one_sp(3.0, 46.5, 0.9990, 700000.0, 6600000.0, Ellipsoid::Grs80);
+        assert!((lcc.forward(&LatLon::new(46.5, 5.0).unwrap()).scale - 0.9990).abs() < 1e-12);
+
+        let p = lcc.forward(&LatLon::new(48.8582, 2.2945).unwrap());
+        let ll = lcc.inverse(p.easting, p.northing);
+        assert!((ll.lat - 48.8582).abs() < 1e-10);
+        assert!((ll.lon - 2.2945).abs() < 1e-10);
+    }
+
+    #[test]
+    fn guidance_note_two_sp() {
+        // IOGP Guidance Note 7-2, NAD27 / Texas South Central, in US survey feet
+        let ft = 1200.0 / 3937.0;
+        let lcc = LambertConformalConic::two_sp(-99.0, 27.0 + 50.0 / 60.0, 28.0 + 23.0 / 60.0, 30.0 + 17.0 / 60.0,
+                                                2000000.0 * ft, 0.0, Ellipsoid::Clarke1866);
+        let p = lcc.forward(&LatLon { lat: 28.5, lon: -96.0, ..Default::default() });
+        assert!((p.easting / ft - 2963503.91).abs() < 0.01);
+        assert!((p.northing / ft - 254759.80).abs() < 0.01);
+
+        let ll = lcc.inverse(2963503.91 * ft, 254759.80 * ft);
+        assert!((ll.lat - 28.5).abs() < 1e-7);
+        assert!((ll.lon - -96.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn guidance_note_one_sp() {
+        // IOGP Guidance Note 7-2, JAD69 / Jamaica National Grid
+        let lcc = LambertConformalConic::one_sp(-77.0, 18.0, 1.0, 250000.0, 150000.0, Ellipsoid::Clarke1866);
+        let lat = 17.0 + 55.0 / 60.0 + 55.80 / 3600.0;
+        let lon = -(76.0 + 56.0 / 60.0 + 37.26 / 3600.0);
+        let p = lcc.forward(&LatLon { lat, lon, ..Default::default() });
+        assert!((p.easting - 255966.58).abs() < 0.01);
+        assert!((p.northing - 142493.51).abs() < 0.01);
+
+        let ll = lcc.inverse(255966.58, 142493.51);
+        assert!((ll.lat - lat).abs() < 1e-7);
+        assert!((ll.lon - lon).abs() < 1e-7);
+    }
+}
@@ -26,19 +26,62 @@ mod hemisphere;
 mod band;
 mod col;
 mod row;
+mod zone_policy;
+mod ellipsoid;
+mod tm;
+mod gauss_kruger;
+mod osgb;
+mod irish_grid;
+mod usng;
+mod lcc;
+mod spcs;
+mod web_mercator;
+mod direction;
+mod geohash;
+mod maidenhead;
+mod polygon;
+mod covering;
+mod grid_lines;
+mod gars;
+mod plus_code;
+mod georef;
+mod omerc;
+mod swiss;
 
 pub use errors::Errors;
 pub use band::LatBand;
-pub use gzd::Gzd;
+pub use gzd::{Gzd, GridSquareId100k, Square100k};
 pub use utm::Utm;
 pub use mgrs::Mgrs;
-pub use accuracy::Accuracy;
+pub use accuracy::{Accuracy, RoundingMode};
 pub use latlon::LatLon;
+pub use datum::Datum;
+pub use row::Lettering;
+pub use zone_policy::ZonePolicy;
+pub use ellipsoid::Ellipsoid;
+pub use tm::{TransverseMercator, TmCoord};
+pub use gauss_kruger::{GaussKruger, ZoneWidth};
+pub use osgb::Osgb;
+pub use irish_grid::{IrishGrid, Itm};
+pub use usng::{Usng, Truncation};
+pub use lcc::{LambertConformalConic, LccCoord};
+pub use spcs::{StatePlane, StatePlaneZone, SpcsProjection, LinearUnit};
+pub use web_mercator::{WebMercator, Tile};
+pub use direction::Direction;
+pub use geohash::Geohash;
+pub use maidenhead::Maidenhead;
+pub use gars::{Gars, GarsPrecision};
+pub use plus_code::PlusCode;
+pub use georef::{Georef, GeorefPrecision};
+pub use omerc::{HotineObliqueMercator, OmercCoord};
+pub use swiss::{Lv03, Lv95};
+pub use covering::MgrsCells;
+pub use grid_lines::{GridLine, GridLineKind, GridLabel};
 
 pub type Lat = f64;
 impl From<LatBand> for f64 {
     fn from(band: LatBand) -> Self {
-        ((band.index() - 10) * 8) as f64
+        (band.index() as f64 - 10.0) * 8.0
     }
 }
 pub type Lon = f64;
@@ -1,22 +1,26 @@
 #[cfg(feature = "debug")]
+#[allow(unused_macros)]
 macro_rules! debugln {
     ($fmt:expr) => (println!(concat!("**DEBUG** ", $fmt)));
     ($fmt:expr, $($arg:tt)*) => (println!(concat!("**DEBUG** ",$fmt), $($arg)*));
 }
 
 #[cfg(feature = "debug")]
+#[allow(unused_macros)]
 macro_rules! debug {
     ($fmt:expr) => (print!(concat!("**DEBUG** ", $fmt)));
     ($fmt:expr, $($arg:tt)*) => (println!(concat!("**DEBUG** ",$fmt), $($arg)*));
 }
 
 #[cfg(not(feature = "debug"))]
+#[allow(unused_macros)]
 macro_rules! debugln {
     ($fmt:expr) => ();
     ($fmt:expr, $($arg:tt)*) => ();
 }
 
 #[cfg(not(feature = "debug"))]
+#[allow(unused_macros)]
 macro_rules! debug {
     ($fmt:expr) => ();
     ($fmt:expr, $($arg:tt)*) => ();
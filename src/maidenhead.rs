@@ -0,0 +1,185 @@
+use std::str::FromStr;
+use std::fmt;
+
+use ascii;
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+
+// number of divisions each character pair splits its parent cell into: field (A-R), square
+// (0-9), subsquare (a-x), extended square (0-9), extended subsquare (a-x)
+const DIVISIONS: [u32; 5] = [18, 10, 24, 10, 24];
+
+/// Maidenhead locator, such as `JN88dc`
+///
+/// Locators are 2 to 10 characters long, each pair of characters dividing the parent cell into
+/// letters or digits alternately, starting from 20° x 10° fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Maidenhead {
+    locator: String,
+}
+
+impl Maidenhead {
+    pub fn encode(ll: &LatLon, len: usize) -> Self {
+        /*!
+        Encodes a WGS84 latitude/longitude as a Maidenhead locator.
+
+        ### Params
+         * **ll**: The point to encode.
+         * **len**: Number of characters, 2, 4, 6, 8 or 10.
+
+        # Panics
+
+        If `len` is not one of 2, 4, 6, 8 or 10.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(48.1466, 16.3045).unwrap();
+        let locator = Maidenhead::encode(&ll, 6); // locator.to_string() = 'JN88dd'
+        ```
+        */
+
+        if !((2..=10).contains(&len) && len.is_multiple_of(2)) { panic!("Invalid Maidenhead locator length {}", len); }
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        // count from the south-west corner of the world, keeping the north pole in the top field
+        let mut lon = (ll.lon + 180.0).rem_euclid(360.0) / 360.0;
+        let mut lat = f64::min((ll.lat + 90.0) / 180.0, 1.0 - 1e-15);
+
+        let mut locator = String::with_capacity(len);
+        for (pair, &n) in DIVISIONS[..len / 2].iter().enumerate() {
+            let x = f64::floor(lon * n as f64) as u8;
+            let y = f64::floor(lat * n as f64) as u8;
+            locator.push(Maidenhead::symbol(pair, x));
+            locator.push(Maidenhead::symbol(pair, y));
+            lon = lon * n as f64 - x as f64;
+            lat = lat * n as f64 - y as f64;
+        }
+        Maidenhead { locator }
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Returns the square of this locator.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values.
+        */
+
+        let (mut lon, mut lat) = (0.0, 0.0);
+        let (mut width, mut height) = (360.0, 180.0);
+        let bytes = self.locator.as_bytes();
+        for (pair, &n) in DIVISIONS[..bytes.len() / 2].iter().enumerate() {
+            width /= n as f64;
+            height /= n as f64;
+            lon += Maidenhead::index(pair, bytes[pair * 2]) as f64 * width;
+            lat += Maidenhead::index(pair, bytes[pair * 2 + 1]) as f64 * height;
+        }
+
+        [LatLon { lat: lat - 90.0, lon: lon - 180.0, ..Default::default() },
+         LatLon { lat: lat + height - 90.0, lon: lon + width - 180.0, ..Default::default() }]
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Decodes this locator to the center of its square.
+        */
+
+        let rect = self.to_ll_rect();
+        LatLon {
+            lat: (rect[0].lat + rect[1].lat) / 2.0,
+            lon: (rect[0].lon + rect[1].lon) / 2.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.locator.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locator.is_empty()
+    }
+
+    fn symbol(pair: usize, i: u8) -> char {
+        // fields are upper case, subsquares conventionally lower case
+        match pair {
+            0 => (ascii::A + i) as char,
+            _ if pair % 2 == 1 => (b'0' + i) as char,
+            _ => (b'a' + i) as char,
+        }
+    }
+
+    fn index(pair: usize, b: u8) -> u8 {
+        match pair % 2 {
+            1 => b - b'0',
+            _ => b.to_ascii_uppercase() - ascii::A,
+        }
+    }
+}
+
+impl From<Maidenhead> for LatLon {
+    fn from(locator: Maidenhead) -> Self {
+        locator.to_ll()
+    }
+}
+
+impl FromStr for Maidenhead {
+    type Err = Errors;
+    /// Parses a Maidenhead locator of 2, 4, 6, 8 or 10 characters, in either case.
+    fn from_str(locator: &str) -> Result<Self, Self::Err> {
+        let bytes = locator.as_bytes();
+        if !(bytes.len() >= 2 && bytes.len() <= 10 && bytes.len().is_multiple_of(2)) {
+            return Err(Errors::InvalidGridReference(locator.to_owned()));
+        }
+        for (i, b) in bytes.iter().enumerate() {
+            let pair = i / 2;
+            let valid = match pair % 2 {
+                1 => b.is_ascii_digit(),
+                _ => b.is_ascii_alphabetic() && b.to_ascii_uppercase() - ascii::A < DIVISIONS[pair] as u8,
+            };
+            if !valid { return Err(Errors::InvalidGridReference(locator.to_owned())); }
+        }
+
+        // normalise the case of each pair
+        let locator = bytes.iter().enumerate()
+            .map(|(i, b)| Maidenhead::symbol(i / 2, Maidenhead::index(i / 2, *b)))
+            .collect();
+        Ok(Maidenhead { locator })
+    }
+}
+
+impl fmt::Display for Maidenhead {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.locator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Maidenhead;
+    use latlon::LatLon;
+
+    #[test]
+    fn encode_and_decode() {
+        let ll = LatLon::new(48.1466, 16.3045).unwrap();
+        assert_eq!(&*Maidenhead::encode(&ll, 2).to_string(), "JN");
+        assert_eq!(&*Maidenhead::encode(&ll, 4).to_string(), "JN88");
+        assert_eq!(&*Maidenhead::encode(&ll, 6).to_string(), "JN88dd");
+
+        let locator: Maidenhead = "jn88DC".parse().unwrap();
+        assert_eq!(&*locator.to_string(), "JN88dc");
+        let rect = locator.to_ll_rect();
+        assert!((rect[0].lon - (16.0 + 3.0 * 5.0 / 60.0)).abs() < 1e-12);
+        assert!((rect[0].lat - (48.0 + 2.0 * 2.5 / 60.0)).abs() < 1e-12);
+        assert!((rect[1].lon - rect[0].lon - 5.0 / 60.0).abs() < 1e-12);
+
+        let ll = locator.to_ll();
+        assert_eq!(&Maidenhead::encode(&ll, 10).to_string()[..6], "JN88dc");
+
+        assert!("JS88".parse::<Maidenhead>().is_err());
+        assert!("JN8".parse::<Maidenhead>().is_err());
+        assert!("JN88dz".parse::<Maidenhead>().is_err());
+    }
+}
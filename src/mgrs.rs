@@ -3,14 +3,21 @@ use std::fmt;
 
 use Utm;
 use Accuracy;
+use accuracy::RoundingMode;
 use gzd::{Gzd, GridSquareId100k};
 use LatLon;
 use parser::MgrsParser;
-use Lat;
-use row::RowLetter;
+use row::{RowLetter, Lettering};
 use col::ColLetter;
 use band::LatBand;
 use datum::Datum;
+use Errors;
+use web_mercator::Tile;
+use direction::Direction;
+use polygon;
+use covering::{MgrsCells, zone_projection};
+use tm::TmCoord;
+use grid_lines::{self, GridLine};
 
 fn get_accuracy(e: usize, n: usize) -> Option<Accuracy> {
     /*!
@@ -31,15 +38,18 @@ fn get_accuracy(e: usize, n: usize) -> Option<Accuracy> {
     */
     let e_s = format!("{0:0>5}", e);
     let n_s = format!("{0:0>5}", n);
-    let e_st = e_s.trim_right_matches('0');
-    let n_st = n_s.trim_right_matches('0');
-    if e_st.len() >= n_st.len() {
-        Accuracy::from_num_digits(e_st.len())
-    } else {
-        Accuracy::from_num_digits(n_st.len())
-    }
+    let e_st = e_s.trim_end_matches('0');
+    let n_st = n_s.trim_end_matches('0');
+    // a grid reference has at least one digit per coordinate, and twice as many digits in total
+    let digits = usize::max(1, usize::max(e_st.len(), n_st.len()));
+    Accuracy::from_num_digits(2 * digits)
 }
 
+/// MGRS grid reference, such as `31U DQ 48251 11932`
+///
+/// A grid reference carries no datum. Converting with `Utm::from(Mgrs)` assumes WGS84 and the AA
+/// lettering; references read from maps on a legacy datum, whose 100km squares use the AL
+/// lettering (see `Datum::lettering`), must be converted with `Utm::from_mgrs_with_datum`.
 #[derive(Default, Copy, Clone, Debug)]
 pub struct Mgrs {
     pub gzd: Gzd,
@@ -50,11 +60,10 @@ pub struct Mgrs {
 }
 
 impl Mgrs {
-    fn new<L, R, C, D>(zone: u8, band: L, e100k: R, n100k: C, easting: usize, northing: usize, datum: D) -> Self
+    pub fn new<L, C, R>(zone: u8, band: L, e100k: C, n100k: R, easting: usize, northing: usize) -> Self
         where L: Into<LatBand>,
-              R: Into<RowLetter>,
               C: Into<ColLetter>,
-              D: Into<Datum> {
+              R: Into<RowLetter> {
         /*!
         Creates an Mgrs grid reference object.
 
@@ -66,7 +75,6 @@ impl Mgrs {
          * **n100k**: Second letter (N) of 100km grid square.
          * **easting**: Easting in metres within 100km grid square (without leading `0`s).
          * **northing**: Northing in metres within 100km grid square (without leading `0`s).
-         * **datum**: Datum UTM coordinate is based on.
 
         # Panics
 
@@ -81,28 +89,545 @@ impl Mgrs {
         */
 
         Mgrs {
-            gzd: Gzd { zone: zone, band: band.into() },
-            gsid_100k: GridSquareId100k { row: e100k.into(), col: n100k.into() },
-            easting: easting,
-            northing: northing,
+            gzd: Gzd { zone, band: band.into() },
+            gsid_100k: GridSquareId100k { col: e100k.into(), row: n100k.into() },
+            easting,
+            northing,
             accuracy: get_accuracy(easting, northing).expect("Invalid MGRS grid")
         }
     }
 
+    pub fn to_ll_polygon(&self) -> Vec<LatLon> {
+        /*!
+        Returns the outline of this grid reference's cell in latitude/longitude.
+
+        Grid lines are curved in latitude/longitude, so each edge of the cell is sampled at
+        several points.
+
+        ### Return
+         * The corners and edge points of the cell, anticlockwise from the bottom-left.
+        */
+
+        let utm = Utm::from(*self);
+        let size = self.accuracy.as_distance() as i32;
+        let steps = 4;
+
+        let mut edge = Vec::with_capacity(4 * steps as usize);
+        for i in 0..steps { edge.push((size * i / steps, 0)); }
+        for i in 0..steps { edge.push((size, size * i / steps)); }
+        for i in 0..steps { edge.push((size - size * i / steps, size)); }
+        for i in 0..steps { edge.push((0, size - size * i / steps)); }
+
+        edge.iter()
+            .map(|&(de, dn)| LatLon::from(Utm { easting: utm.easting + de, northing: utm.northing + dn, ..utm }))
+            .collect()
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Conversion of MGRS to lat/lon.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values,
+           representing the bounding box for the provided MGRS reference.
+        */
+
+        polygon::bounds(&self.to_ll_polygon())
+    }
+
+    pub fn tiles(&self, zoom: u8) -> Vec<Tile> {
+        /*!
+        Returns the XYZ map tiles covering this grid reference's cell.
+
+        ### Params
+         * **zoom**: Zoom level of the tiles.
+
+        ### Return
+         * The tiles overlapping the cell's bounding box, row by row from the north-west.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let tiles = mgrs.tiles(12); // tiles[0].to_string() = '12/2074/1409'
+        ```
+        */
+
+        let rect = self.to_ll_rect();
+        let sw = Tile::from_ll(&rect[0], zoom);
+        let ne = Tile::from_ll(&rect[1], zoom);
+
+        let mut tiles = Vec::new();
+        for y in ne.y..sw.y + 1 {
+            for x in sw.x..ne.x + 1 {
+                tiles.push(Tile { x, y, z: zoom });
+            }
+        }
+        tiles
+    }
+
+    pub fn neighbour(&self, direction: Direction) -> Option<Mgrs> {
+        /*!
+        Returns the adjacent grid reference at the same `Accuracy`.
+
+        The neighbour is the cell containing the center of the next cell over in this reference's
+        grid. Across a 100km square or band boundary that is simply the next cell; across a UTM
+        zone boundary (including the irregular Norway and Svalbard zones) it is the cell of the
+        neighbouring zone's grid, which need not line up with this one.
+
+        ### Params
+         * **direction**: Which of the 8 neighbours to return.
+
+        ### Returns
+         * **Some**: The neighbouring grid reference.
+         * **None**: If the neighbour would lie outside of the UTM latitude range of 80°S..84°N.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let n = mgrs.neighbour(Direction::NE).unwrap(); // n.to_string() = '31U DQ 49 12'
+        ```
+        */
+
+        let utm = Utm::from(*self);
+        let size = self.accuracy.as_distance() as i32;
+        let (de, dn) = direction.as_offset();
+        let centre = Utm {
+            easting: utm.easting + de * size + size / 2,
+            northing: utm.northing + dn * size + size / 2,
+            ..utm
+        };
+
+        let ll = LatLon::from(centre);
+        if !(-80.0 <= ll.lat && ll.lat <= 84.0) { return None; }
+
+        // snap to the cell in whichever zone the center falls within
+        let mgrs = Utm::from_ll(&ll).to_mgrs(self.accuracy);
+        Some(Mgrs {
+            easting: mgrs.easting / size as usize * size as usize,
+            northing: mgrs.northing / size as usize * size as usize,
+            ..mgrs
+        })
+    }
+
+    pub fn offset(&self, de: f64, dn: f64) -> Result<Mgrs, Errors> {
+        /*!
+        Moves this grid reference by a distance along the grid, re-resolving the 100km square,
+        band and, if the move crosses into another, the zone. See `Utm::offset`.
+
+        ### Params
+         * **de**: Distance to move east (negative for west), in metres along grid east.
+         * **dn**: Distance to move north (negative for south), in metres along grid north.
+
+        ### Returns
+         * **Ok**: The moved grid reference, at the same `Accuracy`.
+         * **Err**: `Errors::InvalidLatitude` if the moved point lies outside of the UTM
+           latitude range of 80°S..84°N.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48251 11932".parse().unwrap();
+        let moved = mgrs.offset(350.0, 1200.0).unwrap(); // moved.to_string() = '31U DQ 48601 13132'
+        ```
+        */
+
+        Ok(Utm::from(*self).offset(de, dn)?.to_mgrs(self.accuracy))
+    }
+
+    pub fn with_accuracy(&self, accuracy: Accuracy, mode: RoundingMode) -> Mgrs {
+        /*!
+        Brings this grid reference to a different `Accuracy`, moving its easting/northing onto the
+        new grid so the digits shown and the position held agree.
+
+        Rounding to the nearest cell can carry into the next 100km square, band or zone.
+
+        ### Params
+         * **accuracy**: Precision of the returned grid reference.
+         * **mode**: How the easting/northing are brought onto the new grid; `Center` gives the
+           cell at the center of this one at a finer accuracy, and the cell containing it
+           otherwise. The result always holds the south-west corner of its cell, see `center`.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48251 11932".parse().unwrap();
+        let t = mgrs.with_accuracy(Accuracy::OneThousand, RoundingMode::Truncate); // t.to_string() = '31U DQ 48 11'
+        let n = mgrs.with_accuracy(Accuracy::OneThousand, RoundingMode::Nearest); // n.to_string() = '31U DQ 48 12'
+        let c = mgrs.with_accuracy(Accuracy::OneThousand, RoundingMode::Center); // c.to_string() = '31U DQ 48 11'
+        ```
+        */
+
+        let (size, current) = (accuracy.as_distance(), self.accuracy.as_distance());
+        let round = |v: usize| match mode {
+            RoundingMode::Truncate => v / size * size,
+            RoundingMode::Nearest => (v + size / 2) / size * size,
+            // the center of the current cell always lies within its 100km square
+            RoundingMode::Center => (v / current * current + current / 2) / size * size,
+        };
+        let (easting, northing) = (round(self.easting), round(self.northing));
+        if easting < 100000 && northing < 100000 {
+            return Mgrs { easting, northing, accuracy, ..*self };
+        }
+
+        // carry into the next 100km square, then snap to that square's grid
+        let de = easting as f64 - self.easting as f64;
+        let dn = northing as f64 - self.northing as f64;
+        match self.offset(de, dn) {
+            Ok(moved) => moved.with_accuracy(accuracy, RoundingMode::Truncate),
+            // beyond 84°N, where there is no next square to carry into
+            Err(_) => self.with_accuracy(accuracy, RoundingMode::Truncate),
+        }
+    }
+
+    pub fn center_at(&self, accuracy: Accuracy) -> Mgrs {
+        /*!
+        Returns the center of this grid reference's cell, such as to give a coarse reference at a
+        finer `Accuracy`.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let center = mgrs.center_at(Accuracy::One); // center.to_string() = '31U DQ 48500 11500'
+        ```
+        */
+
+        self.with_accuracy(accuracy, RoundingMode::Center)
+    }
+
+    pub fn parent(&self) -> Option<Mgrs> {
+        /*!
+        Returns the cell one `Accuracy` coarser containing this grid reference's cell.
+
+        ### Returns
+         * **Some**: The parent, such as `31U DQ 4 1` for `31U DQ 48 11`, or `31U DQ` for
+           `31U DQ 4 1`.
+         * **None**: If this is a 100km square, whose parent is its GZD, `self.gzd` (see
+           `Gzd::children`).
+        */
+
+        let accuracy = Accuracy::from_distance(self.accuracy.as_distance() * 10)?;
+        Some(self.with_accuracy(accuracy, RoundingMode::Truncate))
+    }
+
+    pub fn children(&self) -> Vec<Mgrs> {
+        /*!
+        Returns the 100 cells one `Accuracy` finer within this grid reference's cell, row by row
+        from the south-west, or none if this is a 1m cell.
+        */
+
+        let accuracy = match Accuracy::from_distance(self.accuracy.as_distance() / 10) {
+            Some(accuracy) => accuracy,
+            None => return Vec::new(),
+        };
+        let size = accuracy.as_distance();
+        let sw = self.with_accuracy(self.accuracy, RoundingMode::Truncate);
+
+        let mut children = Vec::with_capacity(100);
+        for row in 0..10 {
+            for col in 0..10 {
+                children.push(Mgrs {
+                    easting: sw.easting + col * size,
+                    northing: sw.northing + row * size,
+                    accuracy,
+                    ..sw
+                });
+            }
+        }
+        children
+    }
+
+    pub fn contains(&self, other: &Mgrs) -> bool {
+        /*!
+        Returns whether another grid reference's cell lies within this one's (or is the same
+        cell), such as `31U DQ 48 11` containing `31U DQ 482 119`. See `Gzd::contains_mgrs` for
+        the level above the 100km square.
+        */
+
+        let ids = self.cell_ids();
+        ids.start <= other.cell_id() && other.cell_id() < ids.end
+    }
+
+    pub fn cell_id(&self) -> u64 {
+        /*!
+        Returns a compact ID for this grid reference's cell, which sorts spatially.
+
+        IDs order by zone, then band, then 100km square (row by row from the south-west), then by
+        the easting and northing digits interleaved. Each cell's ID is immediately followed by the
+        IDs of every cell within it, see `Mgrs::cell_ids`; a GZD's own ID (`Gzd::cell_id`)
+        precedes those of its 100km squares.
+
+        ### Returns
+         * The ID, which `Mgrs::from_cell_id` converts back.
+
+        # Examples
+
+        ```
+        let parent: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let child: Mgrs = "31U DQ 482 119".parse().unwrap();
+        assert!(parent.cell_id() < child.cell_id() && child.cell_id() < parent.cell_ids().end);
+        ```
+        */
+
+        let utm = Utm::from(*self);
+        let square = (utm.northing / 100000) as u64 * 10 + (utm.easting / 100000) as u64;
+
+        // interleave the digits of the easting and northing within the square, e.g. 48251 11932
+        // becomes 41 81 29 53 12
+        let size = self.accuracy.as_distance();
+        let (easting, northing) = (self.easting / size * size, self.northing / size * size);
+        let mut digits = 0;
+        for i in (0..5).rev() {
+            let unit = usize::pow(10, i);
+            digits = digits * 100 + (easting / unit % 10 * 10 + northing / unit % 10) as u64;
+        }
+
+        self.gzd.cell_id() | square << 37 | digits << 3 | cell_level(self.accuracy)
+    }
+
+    pub fn cell_ids(&self) -> ::std::ops::Range<u64> {
+        /*!
+        Returns the range of cell IDs of this grid reference's cell and every cell within it, for
+        range queries on a store keyed by `Mgrs::cell_id`.
+        */
+
+        let level = cell_level(self.accuracy);
+        let id = self.cell_id();
+        id..(id - level + (u64::pow(100, 6 - level as u32) << 3))
+    }
+
+    pub fn from_cell_id(id: u64) -> Result<Mgrs, Errors> {
+        /*!
+        Converts a cell ID from `Mgrs::cell_id` back to a grid reference.
+
+        ### Returns
+         * **Ok**: The grid reference of the cell.
+         * **Err**: `Errors::InvalidGridReference` if the ID is not that of an MGRS cell, including
+           the ID of a GZD (see `Gzd::from_cell_id`).
+        */
+
+        let invalid = || Errors::InvalidGridReference(id.to_string());
+        // range check the zone bits before narrowing them
+        let zone = id >> 52;
+        if !(1..=60).contains(&zone) { return Err(invalid()); }
+        let zone = zone as u8;
+        let band = LatBand::from_index((id >> 47 & 0x1f) as usize).ok_or_else(invalid)?;
+        let square = id >> 37 & 0x3ff;
+        let mut digits = id >> 3 & 0x3_ffff_ffff;
+        let level = (id & 0x7) as usize;
+        if level == 0 { return Err(invalid()); }
+        let accuracy = Accuracy::from_numeric(7 - level).ok_or_else(invalid)?;
+        if !(1..=8).contains(&(square % 10)) || square >= 1000 {
+            return Err(invalid());
+        }
+
+        let (mut easting, mut northing) = (0, 0);
+        for i in 0..5 {
+            let unit = usize::pow(10, i);
+            easting += (digits / 10 % 10) as usize * unit;
+            northing += (digits % 10) as usize * unit;
+            digits /= 100;
+        }
+        if digits != 0 || easting % accuracy.as_distance() != 0 || northing % accuracy.as_distance() != 0 {
+            return Err(invalid());
+        }
+
+        Ok(Mgrs {
+            gzd: Gzd { zone, band },
+            gsid_100k: GridSquareId100k {
+                col: ColLetter::from_zone_and_easting(zone, (square % 10 * 100000) as i32),
+                row: RowLetter::from_zone_and_northing(zone, (square / 10 * 100000) as i32),
+            },
+            easting,
+            northing,
+            accuracy,
+        })
+    }
+
+    pub fn contains_latlon(&self, ll: &LatLon) -> bool {
+        /*!
+        Returns whether a latitude/longitude falls within this grid reference's cell, clipped to
+        its GZD. Points on the west and south edges of a cell belong to it, those on the east and
+        north edges to its neighbours.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        assert!(mgrs.contains_latlon(&LatLon::new(48.8582, 2.2945).unwrap()));
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        if !self.gzd.contains(&ll) { return false; }
+
+        let (e0, n0, e1, n1) = self.grid_rect();
+        let p = zone_projection(&self.gzd).forward(&ll);
+        e0 <= p.easting && p.easting < e1 && n0 <= p.northing && p.northing < n1
+    }
+
+    pub fn area(&self) -> f64 {
+        /*!
+        Returns the area of this grid reference's cell clipped to its GZD, in square metres on the
+        grid; cells cut by a zone or band boundary are smaller than `Accuracy` squared.
+        */
+
+        let (e0, n0, e1, n1) = self.grid_rect();
+        clipped_area(&self.gzd, e0, n0, e1, n1)
+    }
+
+    pub fn intersects(&self, other: &Mgrs) -> bool {
+        /*!
+        Returns whether this grid reference's cell and another's share any area, whatever their
+        `Accuracy`. Cells which only touch do not intersect.
+        */
+
+        self.intersection_area(other) > 0.0
+    }
+
+    pub fn intersection_area(&self, other: &Mgrs) -> f64 {
+        /*!
+        Returns the area shared by this grid reference's cell and another's, each clipped to its
+        GZD, in square metres on the grid.
+
+        GZDs do not overlap, so cells of different GZDs never share any area, even if they refer to
+        the same square of the same zone's grid (such as `31T GP 2 2` and `31U GP 2 2`).
+
+        # Examples
+
+        ```
+        let coarse: Mgrs = "31U DQ 4 1".parse().unwrap();
+        let fine: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let area = coarse.intersection_area(&fine); // area = 1000000
+        ```
+        */
+
+        if self.gzd != other.gzd { return 0.0; }
+        let (a, b) = (self.grid_rect(), other.grid_rect());
+        let (e0, n0) = (f64::max(a.0, b.0), f64::max(a.1, b.1));
+        let (e1, n1) = (f64::min(a.2, b.2), f64::min(a.3, b.3));
+        if e0 >= e1 || n0 >= n1 { return 0.0; }
+        clipped_area(&self.gzd, e0, n0, e1, n1)
+    }
+
+    fn grid_rect(&self) -> (f64, f64, f64, f64) {
+        /*!
+        Returns the south-west and north-east corners of this grid reference's cell on its zone's
+        grid, as minimum easting, minimum northing, maximum easting and maximum northing.
+        */
+
+        let utm = Utm::from(self.with_accuracy(self.accuracy, RoundingMode::Truncate));
+        let size = self.accuracy.as_distance() as f64;
+        let (e, n) = (utm.easting as f64, utm.northing as f64);
+        (e, n, e + size, n + size)
+    }
+
+    pub fn relettered(&self, from: Lettering, to: Lettering) -> Mgrs {
+        /*!
+        Converts this grid reference from one 100km square lettering scheme to another, such as to
+        bring a reference read from an older map into line with modern ones on the same datum.
+
+        # Examples
+
+        ```
+        let legacy: Mgrs = "18S UU 23445 06273".parse().unwrap();
+        let mgrs = legacy.relettered(Lettering::AL, Lettering::AA); // mgrs.to_string() = '18S UJ 23445 06273'
+        ```
+        */
+
+        let row = self.gsid_100k.row.relettered(self.gzd.zone, from, to);
+        Mgrs { gsid_100k: GridSquareId100k { row, ..self.gsid_100k }, ..*self }
+    }
+
+    pub fn neighbours(&self) -> Vec<Mgrs> {
+        /*!
+        Returns the adjacent grid references at the same `Accuracy`, clockwise from north, leaving
+        out any beyond the UTM latitude range. See `Mgrs::neighbour`.
+        */
+
+        Direction::all().iter().filter_map(|d| self.neighbour(*d)).collect()
+    }
+
+    pub fn covering(area: &[LatLon], accuracy: Accuracy) -> MgrsCells {
+        /*!
+        Returns the grid references whose cells overlap an area, such as to lay out a search grid.
+
+        Cells along a GZD boundary are clipped to it, so a cell straddling two GZDs is returned
+        once for each, with the same easting/northing on its own zone's grid.
+
+        ### Params
+         * **area**: Outline of the area; it may be concave but must not cross the antimeridian.
+         * **accuracy**: Size of the cells to return.
+
+        ### Return
+         * An iterator generating the cells as it goes, GZD by GZD from 1C, and row by row from
+           the south-west within each.
+
+        # Examples
+
+        ```
+        let area = [LatLon::new(48.85, 2.28).unwrap(), LatLon::new(48.85, 2.30).unwrap(),
+                    LatLon::new(48.87, 2.29).unwrap()];
+        let cells = Mgrs::covering(&area, Accuracy::OneThousand).count(); // cells = 6
+        ```
+        */
+
+        MgrsCells::new(area, accuracy)
+    }
+
+    pub fn covering_rect(rect: &[LatLon; 2], accuracy: Accuracy) -> MgrsCells {
+        /*!
+        Returns the grid references whose cells overlap a latitude/longitude bounding box. See
+        `Mgrs::covering`.
+
+        ### Params
+         * **rect**: Bottom-left and top-right of the bounding box.
+         * **accuracy**: Size of the cells to return.
+        */
+
+        let corners = [rect[0],
+                       LatLon { lon: rect[1].lon, ..rect[0] },
+                       rect[1],
+                       LatLon { lon: rect[0].lon, ..rect[1] }];
+        MgrsCells::new(&corners, accuracy)
+    }
+
+    pub fn grid_lines(viewport: &[LatLon; 2], accuracy: Accuracy) -> Result<Vec<GridLine>, Errors> {
+        /*!
+        Returns the lines of an MGRS map overlay within a viewport: the UTM zone and latitude band
+        boundaries, the 100km square boundaries and, finer than 100km, the grid lines at
+        `accuracy`.
+
+        Each line is labelled with the GZD, 100km square or digits it bounds, see `GridLine`.
+        Lines are cut at GZD boundaries, as each GZD has its own grid.
+
+        ### Params
+         * **viewport**: Bottom-left and top-right of the area shown; it must not cross the
+           antimeridian.
+         * **accuracy**: Spacing of the finest lines; choose it to suit the viewport.
+
+        ### Returns
+         * **Ok**: The lines.
+         * **Err**: `Errors::TooManyGridLines` if the viewport would need more than 10000 lines at
+           `accuracy`, as a 1m grid over a large area would.
+
+        # Examples
+
+        ```
+        let viewport = [LatLon::new(48.7, 2.2).unwrap(), LatLon::new(48.9, 3.2).unwrap()];
+        let lines = Mgrs::grid_lines(&viewport, Accuracy::OneThousand).unwrap();
+        let labels: Vec<String> = lines.iter()
+            .filter(|l| l.kind == GridLineKind::Square100k)
+            .flat_map(|l| l.labels.iter().map(|label| label.text.clone()))
+            .collect(); // labels = ['EP', 'EQ', 'DQ', 'EQ']
+        ```
+        */
+
+        grid_lines::for_viewport(viewport, accuracy)
+    }
 
-    // pub fn to_ll_rect(self) -> [LatLon; 2] {
-    //     /*!
-    //     Conversion of MGRS to lat/lon.
-    //
-    //     ### Params
-    //      * **mgrs** Generic object that supports becoming an `Mgrs` struct.
-    //     ### Return
-    //      * An array of `latLon` structs which represents bottom-left, and top-right values in WGS84,
-    //        representing the bounding box for the provided MGRS reference.
-    //     */
-    //     LatLon::rect_from_mgrs(self).expect("failed to convert MGRS to Lat/Lon")
-    // }
-    //
     // pub fn as_ll_rect(&self) -> [LatLon; 2] {
     //     /*!
     //     Conversion of MGRS to lat/lon.
@@ -118,14 +643,71 @@ impl Mgrs {
 
     /// Derives the centerpoint of an MGRS reference
     pub fn to_ll(self) -> LatLon {
-        LatLon::from(self.into())
+        LatLon::from(Utm::from(self))
     }
 
-    /// Derives the centerpoint of an MGRS reference
+    pub fn center(&self) -> LatLon {
+        /*!
+        Returns the center of this grid reference's cell.
+
+        The easting/northing of an `Mgrs` hold the south-west corner of its cell, which is what
+        `to_ll` and `Utm::from` convert.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "31U DQ 52 11".parse().unwrap();
+        let ll = mgrs.center(); // ll.lat = 48.8546, ll.lon = 2.3525
+        ```
+        */
+
+        LatLon::from(self.center_utm())
+    }
+
+    fn center_utm(&self) -> Utm {
+        let utm = Utm::from(*self);
+        let half = self.accuracy.as_distance() as i32 / 2;
+        Utm { easting: utm.easting + half, northing: utm.northing + half, ..utm }
+    }
+
+    // Derives the centerpoint of an MGRS reference
     // pub fn as_ll(&self) -> LatLon {
     //     LatLon::from(self.utm)
     // }
 
+    pub fn to_zone(&self, zone: u8) -> Result<Mgrs, Errors> {
+        /*!
+        Re-expresses this grid reference in a forced UTM zone.
+
+        Points near a zone boundary have two valid references, one in each zone. See
+        `Utm::to_zone` for the projection limits.
+
+        ### Params
+         * **zone**: The UTM zone (1..60 inclusive) to express this reference in.
+
+        ### Returns
+         * **Ok**: The grid reference of the requested zone containing the center of this
+           reference's cell, at the same `Accuracy`.
+         * **Err**: `Errors::InvalidZone` or `Errors::ZoneOutOfRange` as per `Utm::to_zone`. Also
+           `Errors::ZoneOutOfRange` if the point falls outside of the lettered 100km squares of
+           `zone`.
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "24XWT783908".parse().unwrap();
+        let mgrs = mgrs.to_zone(25).unwrap(); // mgrs.to_string() = '25X EN 041 865'
+        ```
+        */
+
+        // the cell containing this one's center, as the grids of the two zones don't line up
+        let utm = self.center_utm().to_zone(zone)?;
+        if utm.easting < 100000 || utm.easting >= 900000 {
+            return Err(Errors::ZoneOutOfRange(zone));
+        }
+        Ok(utm.to_mgrs(self.accuracy))
+    }
+
     fn as_string(&self, accuracy: Accuracy) -> String {
         /*!
         Returns a string representation of an MGRS grid reference.
@@ -138,7 +720,7 @@ impl Mgrs {
 
         ### Params
          * **accuracy** Precision of returned grid reference (eg `One` = 1m or 10 digit grid,
-         `Ten` = 10m or 8 digit grid, etc.).
+           `Ten` = 10m or 8 digit grid, etc.).
 
         ### Returns
          * This grid reference in standard format.
@@ -152,12 +734,16 @@ impl Mgrs {
         ```
         */
 
-        let digits: i32 = accuracy.as_num_digits() as i32 / 2;
-        // set required precision
-        let easting = (f64::floor(self.easting as f64 / f64::powi(10.0, 5 - digits))) as usize;
-        let northing = (f64::floor(self.northing  as f64 / f64::powi(10.0, 5 - digits))) as usize;
+        // the digits are those of the cell holding the easting/northing, as the military standard
+        // truncates
+        let mgrs = self.with_accuracy(accuracy, RoundingMode::Truncate);
+        let digits = accuracy.as_num_digits() / 2;
+        let size = accuracy.as_distance();
+        if digits == 0 {
+            return format!("{0:02}{1} {2}{3}", mgrs.gzd.zone, mgrs.gzd.band, mgrs.gsid_100k.col, mgrs.gsid_100k.row);
+        }
 
-        format!("{0:02}{1} {2}{3} {4:0<6$} {5:0<6$}", self.gzd.zone, self.gzd.band, self.gsid_100k.col, self.gsid_100k.row, easting, northing, digits as usize)
+        format!("{0:02}{1} {2}{3} {4:0>6$} {5:0>6$}", mgrs.gzd.zone, mgrs.gzd.band, mgrs.gsid_100k.col, mgrs.gsid_100k.row, mgrs.easting / size, mgrs.northing / size, digits)
     }
 }
 
@@ -173,9 +759,63 @@ impl Mgrs {
 //     }
 // }
 
+fn cell_level(accuracy: Accuracy) -> u64 {
+    /*!
+    Returns the level held in the low bits of a cell ID: 0 for a GZD (see `Gzd::cell_id`), 1 for
+    a 100km square, up to 6 for a 1m cell.
+    */
+
+    7 - accuracy.as_numeric() as u64
+}
+
+fn clipped_area(gzd: &Gzd, min_e: f64, min_n: f64, max_e: f64, max_n: f64) -> f64 {
+    /*!
+    Returns the area of a rectangle on a GZD's grid clipped to the GZD, in square metres.
+
+    The rectangle's edges are straight on the grid but curved in latitude/longitude, where the
+    GZD is clipped, so they are sampled densely; the clipped outline is then projected back onto
+    the grid to be measured.
+    */
+
+    let rect = match gzd.bounds() {
+        Ok(rect) => rect,
+        Err(_) => return 0.0,
+    };
+    let tm = zone_projection(gzd);
+    let steps = 32;
+    let (de, dn) = (max_e - min_e, max_n - min_n);
+    let mut edge = Vec::with_capacity(4 * steps);
+    for i in 0..steps { edge.push((de * i as f64 / steps as f64, 0.0)); }
+    for i in 0..steps { edge.push((de, dn * i as f64 / steps as f64)); }
+    for i in 0..steps { edge.push((de - de * i as f64 / steps as f64, dn)); }
+    for i in 0..steps { edge.push((0.0, dn - dn * i as f64 / steps as f64)); }
+
+    let outline: Vec<LatLon> = edge.iter().map(|&(e, n)| tm.inverse(min_e + e, min_n + n)).collect();
+    let inside = |ll: &LatLon| rect[0].lat <= ll.lat && ll.lat <= rect[1].lat && rect[0].lon <= ll.lon && ll.lon <= rect[1].lon;
+    if outline.iter().all(inside) { return de * dn; }
+
+    // the GZD's meridians and parallels are curved on the grid, so resample along them too
+    let clipped = polygon::clip_to_rect(&outline, &rect);
+    let mut points: Vec<TmCoord> = Vec::new();
+    for i in 0..clipped.len() {
+        let (a, b) = (&clipped[i], &clipped[(i + 1) % clipped.len()]);
+        let steps = f64::ceil(f64::max((b.lat - a.lat).abs(), (b.lon - a.lon).abs()) / 1e-3).max(1.0);
+        for s in 0..steps as usize {
+            let t = s as f64 / steps;
+            points.push(tm.forward(&LatLon { lat: a.lat + t * (b.lat - a.lat), lon: a.lon + t * (b.lon - a.lon), ..*a }));
+        }
+    }
+    let mut sum = 0.0;
+    for i in 0..points.len() {
+        let (a, b) = (&points[i], &points[(i + 1) % points.len()]);
+        sum += (a.easting - min_e) * (b.northing - min_n) - (b.easting - min_e) * (a.northing - min_n);
+    }
+    (sum / 2.0).abs()
+}
+
 impl<'a> From<&'a Mgrs> for Mgrs {
     fn from(m: &'a Mgrs) -> Self {
-        m.clone()
+        *m
     }
 }
 
@@ -192,7 +832,7 @@ impl FromStr for Mgrs {
 
 impl fmt::Display for Mgrs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", self.as_string(Accuracy::One))
+        write!(f, "{}", self.as_string(self.accuracy))
     }
 }
 
@@ -203,14 +843,199 @@ mod test {
 
     #[test]
     fn getting_accuracy() {
-        assert_eq!(mgrs::get_accuracy(25, 250), Accuracy::One);
-        assert_eq!(mgrs::get_accuracy(5, 25), Accuracy::One);
-        assert_eq!(mgrs::get_accuracy(256, 823), Accuracy::OneHundred);
-        assert_eq!(mgrs::get_accuracy(12345, 354), Accuracy::One);
-        assert_eq!(mgrs::get_accuracy(12000, 123), Accuracy::OneHundred);
-        assert_eq!(mgrs::get_accuracy(1200, 1000), Accuracy::OneThousand);
-        assert_eq!(mgrs::get_accuracy(10000, 1), Accuracy::One);
-        assert_eq!(mgrs::get_accuracy(10000, 20000), Accuracy::TenThousand);
-        assert_eq!(mgrs::get_accuracy(100, 1234), Accuracy::Ten);
+        // significant digits of the 5 digit, zero padded, easting and northing
+        assert_eq!(get_accuracy(25, 250), Some(Accuracy::One));
+        assert_eq!(get_accuracy(5, 25), Some(Accuracy::One));
+        assert_eq!(get_accuracy(256, 820), Some(Accuracy::One));
+        assert_eq!(get_accuracy(12300, 35400), Some(Accuracy::OneHundred));
+        assert_eq!(get_accuracy(12000, 12000), Some(Accuracy::OneThousand));
+        assert_eq!(get_accuracy(1200, 1000), Some(Accuracy::OneHundred));
+        assert_eq!(get_accuracy(10000, 1000), Some(Accuracy::OneThousand));
+        assert_eq!(get_accuracy(10000, 20000), Some(Accuracy::TenThousand));
+        assert_eq!(get_accuracy(0, 0), Some(Accuracy::TenThousand));
+        assert_eq!(get_accuracy(100, 1230), Some(Accuracy::Ten));
+    }
+
+    #[test]
+    fn new() {
+        let mgrs = Mgrs::new(31, 'U', 'D', 'Q', 48251, 11932);
+        assert_eq!(&*mgrs.to_string(), "31U DQ 48251 11932");
+        assert_eq!(Mgrs::new(31, "U", 'D', 'Q', 48000, 11000).accuracy, Accuracy::OneThousand);
+    }
+
+    #[test]
+    fn tiles() {
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        let tiles: Vec<String> = mgrs.tiles(12).iter().map(|t| t.to_string()).collect();
+        assert_eq!(tiles, vec!["12/2074/1409"]);
+
+        // the 1km cell spans 2 columns and 3 rows of tiles at zoom 15, listed from the north-west
+        let tiles: Vec<String> = mgrs.tiles(15).iter().map(|t| t.to_string()).collect();
+        assert_eq!(tiles, vec!["15/16592/11272", "15/16593/11272",
+                               "15/16592/11273", "15/16593/11273",
+                               "15/16592/11274", "15/16593/11274"]);
+    }
+
+    #[test]
+    fn neighbours() {
+        let mgrs: Mgrs = "31U DQ 99 99".parse().unwrap();
+        let n: Vec<String> = mgrs.neighbours().iter().map(|m| m.to_string()).collect();
+        assert_eq!(n, vec!["31U DR 99 00", "31U ER 00 00", "31U EQ 00 99", "31U EQ 00 98",
+                           "31U DQ 99 98", "31U DQ 98 98", "31U DQ 98 99", "31U DR 98 00"]);
+
+        // across the Norway and Svalbard zone boundaries
+        let mgrs: Mgrs = "31V DG 99 51".parse().unwrap();
+        assert_eq!(&*mgrs.neighbour(Direction::E).unwrap().to_string(), "32V JM 66 66");
+        let mgrs: Mgrs = "31X FG 3 6".parse().unwrap();
+        assert_eq!(&*mgrs.neighbour(Direction::E).unwrap().to_string(), "33X UG 6 6");
+    }
+
+    #[test]
+    fn offset() {
+        let mgrs: Mgrs = "31U DQ 48251 11932".parse().unwrap();
+        assert_eq!(&*mgrs.offset(350.0, 1200.0).unwrap().to_string(), "31U DQ 48601 13132");
+        assert_eq!(&*mgrs.offset(60000.0, 90000.0).unwrap().to_string(), "31U ER 08251 01932");
+
+        // into the next zone
+        let mgrs: Mgrs = "31U GR 14268 42915".parse().unwrap();
+        assert_eq!(&*mgrs.offset(2000.0, 0.0).unwrap().to_string(), "32U KA 86293 42812");
+
+        // beyond the UTM limits
+        match "31X DJ 00000 99999".parse::<Mgrs>().unwrap().offset(0.0, 500000.0) {
+            Err(Errors::InvalidLatitude(_)) => (),
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    #[test]
+    fn with_accuracy() {
+        let mgrs: Mgrs = "31U DQ 48251 11932".parse().unwrap();
+        let at = |mode| mgrs.with_accuracy(Accuracy::OneThousand, mode);
+        assert_eq!(&*at(RoundingMode::Truncate).to_string(), "31U DQ 48 11");
+        assert_eq!(&*at(RoundingMode::Nearest).to_string(), "31U DQ 48 12");
+        assert_eq!(&*at(RoundingMode::Center).to_string(), "31U DQ 48 11");
+
+        // carried into the next 100km square
+        let mgrs: Mgrs = "31U DQ 99951 99932".parse().unwrap();
+        assert_eq!(&*mgrs.with_accuracy(Accuracy::OneHundred, RoundingMode::Nearest).to_string(), "31U EQ 000 999");
+
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        assert_eq!(&*mgrs.center_at(Accuracy::One).to_string(), "31U DQ 48500 11500");
+    }
+
+    #[test]
+    fn center_reference() {
+        // a reference brought to a coarser accuracy with `Center` holds the corner of its cell
+        let mgrs: Mgrs = "31U DQ 52345 11678".parse().unwrap();
+        let center = mgrs.with_accuracy(Accuracy::OneThousand, RoundingMode::Center);
+        let truncated: Mgrs = "31U DQ 52 11".parse().unwrap();
+        assert_eq!((center.easting, center.northing), (52000, 11000));
+        assert_eq!(&*center.neighbour(Direction::N).unwrap().to_string(), "31U DQ 52 12");
+
+        let (rect, expected) = (center.to_ll_rect(), truncated.to_ll_rect());
+        for (a, b) in rect.iter().zip(expected.iter()) {
+            assert!((a.lat - b.lat).abs() < 1e-9);
+            assert!((a.lon - b.lon).abs() < 1e-9);
+        }
+
+        // the center is only added on conversion
+        let ll = center.center();
+        let expected = truncated.center_at(Accuracy::One).to_ll();
+        assert!((ll.lat - expected.lat).abs() < 1e-9);
+        assert!((ll.lon - expected.lon).abs() < 1e-9);
+    }
+
+    #[test]
+    fn digits_agree_with_position() {
+        // references from UTM hold the corner of the cell their digits name
+        let utm = Utm::from("31U DQ 48251 11932".parse::<Mgrs>().unwrap());
+        let mgrs = utm.to_mgrs(Accuracy::OneThousand);
+        assert_eq!((mgrs.easting, mgrs.northing), (48000, 11000));
+        assert_eq!(&*mgrs.to_string(), "31U DQ 48 11");
+
+        // a position set off the grid is shown as the cell holding it
+        let mgrs = Mgrs { easting: 48999, northing: 11932, ..mgrs };
+        assert_eq!(&*mgrs.to_string(), "31U DQ 48 11");
+        assert_eq!(mgrs.to_string(), mgrs.with_accuracy(Accuracy::OneThousand, RoundingMode::Truncate).to_string());
+    }
+
+    #[test]
+    fn hierarchy() {
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        assert_eq!(&*mgrs.parent().unwrap().to_string(), "31U DQ 4 1");
+        let square = mgrs.parent().unwrap().parent().unwrap();
+        assert_eq!(&*square.to_string(), "31U DQ");
+        assert_eq!(square.accuracy, Accuracy::OneHundredThousand);
+        assert!(square.parent().is_none());
+        assert_eq!(&*square.children()[0].to_string(), "31U DQ 0 0");
+        assert!(square.contains(&mgrs) && square.cell_ids().end <= "31U EQ".parse::<Mgrs>().unwrap().cell_id());
+
+        // the GZD is the level above the 100km square
+        let squares = mgrs.gzd.children().unwrap();
+        assert_eq!(squares.len(), 54);
+        assert!(squares.iter().any(|s| s.to_string() == square.to_string()));
+        assert!(squares.iter().all(|s| mgrs.gzd.contains_mgrs(s) && mgrs.gzd.cell_id() < s.cell_id()));
+        assert!(mgrs.gzd.contains_mgrs(&mgrs));
+        assert!(!mgrs.gzd.contains_mgrs(&"31T DQ 48 11".parse().unwrap()));
+        assert_eq!(Gzd::from_cell_id(mgrs.gzd.cell_id()).unwrap(), mgrs.gzd);
+        assert!(Gzd::from_cell_id(mgrs.cell_id()).is_err());
+        assert!(Mgrs::from_cell_id(mgrs.gzd.cell_id()).is_err());
+
+        let children = mgrs.children();
+        assert_eq!(children.len(), 100);
+        assert_eq!(&*children[0].to_string(), "31U DQ 480 110");
+        assert_eq!(&*children[99].to_string(), "31U DQ 489 119");
+        assert!(children.iter().all(|c| mgrs.contains(c) && !c.contains(&mgrs)));
+        assert!(!mgrs.contains(&"31U DQ 49 11".parse().unwrap()));
+
+        // descendants sort straight after their ancestor
+        let ids = mgrs.cell_ids();
+        assert!(children.iter().flat_map(|c| c.children()).all(|c| ids.start < c.cell_id() && c.cell_id() < ids.end));
+
+        for s in &["31U DQ 48251 11932", "32V JM 6 6", "31M EV 123 456", "31U DQ"] {
+            let mgrs: Mgrs = s.parse().unwrap();
+            assert_eq!(&*Mgrs::from_cell_id(mgrs.cell_id()).unwrap().to_string(), *s);
+        }
+        assert!(Mgrs::from_cell_id(12345).is_err());
+        // zone bits of 257 must not wrap around to zone 1
+        let mgrs: Mgrs = "01C CV 5 5".parse().unwrap();
+        let wrapped = mgrs.cell_id() | 256 << 52;
+        assert!(Mgrs::from_cell_id(wrapped).is_err());
+        assert!(Gzd::from_cell_id(mgrs.gzd.cell_id() | 256 << 52).is_err());
+    }
+
+    #[test]
+    fn intersection() {
+        let mgrs: Mgrs = "31U DQ 48 11".parse().unwrap();
+        assert!(mgrs.contains_latlon(&LatLon::new(48.8582, 2.2945).unwrap()));
+        assert!(!"31U DQ 48 12".parse::<Mgrs>().unwrap().contains_latlon(&LatLon::new(48.8582, 2.2945).unwrap()));
+
+        let coarse: Mgrs = "31U DQ 4 1".parse().unwrap();
+        assert_eq!(coarse.area(), 1e8);
+        assert_eq!(coarse.intersection_area(&mgrs), 1e6);
+        assert!(coarse.intersects(&mgrs) && mgrs.intersects(&coarse));
+        assert!(!mgrs.intersects(&"31U DQ 49 11".parse().unwrap()));
+
+        // cut by the 31/32 zone boundary and the T/U band boundary
+        let cut: Mgrs = "31U GP 2 2".parse().unwrap();
+        assert!(cut.area() > 3.3e7 && cut.area() < 3.4e7);
+        let sum: f64 = cut.children().iter().map(|c| cut.intersection_area(c)).sum();
+        assert!((sum - cut.area()).abs() < 100.0);
+        assert!(!cut.intersects(&"31T GP 2 2".parse().unwrap()));
+        assert!(!cut.contains_latlon(&LatLon::new(48.01, 6.01).unwrap()));
+    }
+
+    #[test]
+    fn lettering() {
+        let ll = LatLon::new(38.8895, -77.0353).unwrap().to_datum(Datum::Nad27);
+        let utm = Utm::from_ll(&ll);
+        let legacy = utm.to_mgrs(Accuracy::One);
+        assert_eq!(&*legacy.to_string(), "18S UU 23445 06273");
+        assert_eq!(&*legacy.relettered(Lettering::AL, Lettering::AA).to_string(), "18S UJ 23445 06273");
+
+        let back = Utm::from_mgrs_with_datum(&legacy, Datum::Nad27);
+        assert_eq!((back.easting, back.northing), (utm.easting, utm.northing));
+        assert_eq!(back.datum, Datum::Nad27);
+        assert_eq!(Datum::Wgs84.lettering(), Lettering::AA);
     }
 }
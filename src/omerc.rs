@@ -0,0 +1,278 @@
+use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::FRAC_PI_4;
+
+use Lat;
+use Lon;
+use latlon::LatLon;
+use ellipsoid::Ellipsoid;
+
+/// Hotine Oblique Mercator projection with arbitrary parameters
+///
+/// Implements the EPSG formulation (Guidance Note 7-2) of both variant A, whose false
+/// easting/northing are given at the natural origin, and variant B, whose are given at the
+/// projection centre. The Swiss oblique cylindrical projection is variant B with an azimuth and
+/// rectified grid angle of 90°.
+#[derive(Copy, Clone, Debug)]
+pub struct HotineObliqueMercator {
+    /// Longitude of the projection centre, in degrees
+    pub lonc: Lon,
+    /// Latitude of the projection centre, in degrees
+    pub latc: Lat,
+    /// Azimuth of the initial line through the projection centre, in degrees
+    pub azimuth: f64,
+    /// Angle from the rectified grid to the skew (oblique) grid, in degrees
+    pub rectified_grid_angle: f64,
+    /// Scale factor on the initial line
+    pub k0: f64,
+    /// Easting of the natural origin (variant A) or projection centre (variant B), in metres
+    pub false_easting: f64,
+    /// Northing of the natural origin (variant A) or projection centre (variant B), in metres
+    pub false_northing: f64,
+    /// Whether the false easting/northing are given at the projection centre (variant B)
+    pub centre_origin: bool,
+    /// Ellipsoid the projection is defined on
+    pub ellipsoid: Ellipsoid,
+}
+
+/// A point projected onto a Hotine Oblique Mercator grid
+#[derive(Copy, Clone, Debug, Default)]
+pub struct OmercCoord {
+    /// Easting in metres, including the false easting
+    pub easting: f64,
+    /// Northing in metres, including the false northing
+    pub northing: f64,
+    /// Grid scale factor
+    pub scale: f64,
+}
+
+// constants of the projection, derived from its parameters (EPSG notation)
+struct Constants {
+    a: f64,
+    b: f64,
+    h: f64,
+    gamma0: f64,
+    lon0: f64,
+    uc: f64,
+}
+
+impl HotineObliqueMercator {
+    #[allow(clippy::too_many_arguments)]
+    pub fn variant_a(lonc: Lon, latc: Lat, azimuth: f64, rectified_grid_angle: f64, k0: f64,
+                     false_easting: f64, false_northing: f64, ellipsoid: Ellipsoid) -> Self {
+        /*!
+        Creates a projection whose false easting/northing are given at the natural origin, where
+        the initial line meets the equator of the aposphere (EPSG method 9812).
+
+        ### Params
+         * **lonc**: Longitude of the projection centre, in degrees.
+         * **latc**: Latitude of the projection centre, in degrees.
+         * **azimuth**: Azimuth of the initial line through the projection centre, in degrees.
+         * **rectified_grid_angle**: Angle from the rectified to the skew grid, in degrees.
+         * **k0**: Scale factor on the initial line.
+         * **false_easting**: Easting of the natural origin, in metres.
+         * **false_northing**: Northing of the natural origin, in metres.
+         * **ellipsoid**: Ellipsoid the projection is defined on.
+        */
+
+        HotineObliqueMercator {
+            lonc,
+            latc,
+            azimuth,
+            rectified_grid_angle,
+            k0,
+            false_easting,
+            false_northing,
+            centre_origin: false,
+            ellipsoid,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn variant_b(lonc: Lon, latc: Lat, azimuth: f64, rectified_grid_angle: f64, k0: f64,
+                     false_easting: f64, false_northing: f64, ellipsoid: Ellipsoid) -> Self {
+        /*!
+        Creates a projection whose false easting/northing are given at the projection centre
+        (EPSG method 9815).
+
+        ### Params
+         * **lonc**: Longitude of the projection centre, in degrees.
+         * **latc**: Latitude of the projection centre, in degrees.
+         * **azimuth**: Azimuth of the initial line through the projection centre, in degrees.
+         * **rectified_grid_angle**: Angle from the rectified to the skew grid, in degrees.
+         * **k0**: Scale factor on the initial line.
+         * **false_easting**: Easting of the projection centre, in metres.
+         * **false_northing**: Northing of the projection centre, in metres.
+         * **ellipsoid**: Ellipsoid the projection is defined on.
+        */
+
+        HotineObliqueMercator {
+            centre_origin: true,
+            ..HotineObliqueMercator::variant_a(lonc, latc, azimuth, rectified_grid_angle, k0,
+                                               false_easting, false_northing, ellipsoid)
+        }
+    }
+
+    pub fn forward(&self, ll: &LatLon) -> OmercCoord {
+        /*!
+        Projects latitude/longitude onto this grid.
+
+        ### Params
+         * **ll**: The point to project, on this projection's ellipsoid.
+
+        ### Returns
+         * The `OmercCoord` easting, northing and scale of the point.
+        */
+
+        let c = self.constants();
+        let e = self.e();
+        let phi = ll.lat.to_radians();
+        let dlon = c.b * (ll.lon.to_radians() - c.lon0);
+
+        let q = c.h / f64::powf(self.t(phi), c.b);
+        let s = (q - 1.0 / q) / 2.0;
+        let t = (q + 1.0 / q) / 2.0;
+        let v = dlon.sin();
+        let u = (-v * c.gamma0.cos() + s * c.gamma0.sin()) / t;
+
+        // skew coordinates, from the natural origin
+        let v_skew = c.a * f64::ln((1.0 - u) / (1.0 + u)) / (2.0 * c.b);
+        let u_skew = c.a * f64::atan2(s * c.gamma0.cos() + v * c.gamma0.sin(), dlon.cos()) / c.b;
+        // scale of the ellipsoid onto the aposphere, by that of the aposphere onto the grid
+        let esinphi = e * phi.sin();
+        let scale = c.a * f64::sqrt(1.0 - esinphi * esinphi)
+            / (self.ellipsoid.a() * phi.cos() * t * f64::sqrt(1.0 - u * u));
+        let u_skew = if self.centre_origin { u_skew - c.uc } else { u_skew };
+
+        let gammac = self.rectified_grid_angle.to_radians();
+        OmercCoord {
+            easting: v_skew * gammac.cos() + u_skew * gammac.sin() + self.false_easting,
+            northing: u_skew * gammac.cos() - v_skew * gammac.sin() + self.false_northing,
+            scale,
+        }
+    }
+
+    pub fn inverse(&self, easting: f64, northing: f64) -> LatLon {
+        /*!
+        Converts a grid easting/northing back to latitude/longitude.
+
+        ### Params
+         * **easting**: Easting in metres, including the false easting.
+         * **northing**: Northing in metres, including the false northing.
+
+        ### Returns
+         * The `LatLon` of the point, with the scale at that point.
+        */
+
+        let c = self.constants();
+        let e = self.e();
+        let gammac = self.rectified_grid_angle.to_radians();
+        let (x, y) = (easting - self.false_easting, northing - self.false_northing);
+
+        let v_skew = x * gammac.cos() - y * gammac.sin();
+        let mut u_skew = y * gammac.cos() + x * gammac.sin();
+        if self.centre_origin { u_skew += c.uc; }
+
+        let q = f64::exp(-c.b * v_skew / c.a);
+        let s = (q - 1.0 / q) / 2.0;
+        let t = (q + 1.0 / q) / 2.0;
+        let v = f64::sin(c.b * u_skew / c.a);
+        let u = (v * c.gamma0.cos() + s * c.gamma0.sin()) / t;
+        let t = f64::powf(c.h / f64::sqrt((1.0 + u) / (1.0 - u)), 1.0 / c.b);
+
+        // latitude from the conformal latitude, by series
+        let chi = FRAC_PI_2 - 2.0 * t.atan();
+        let (e2, e4, e6, e8) = (e * e, e.powi(4), e.powi(6), e.powi(8));
+        let phi = chi
+            + f64::sin(2.0 * chi) * (e2 / 2.0 + 5.0 * e4 / 24.0 + e6 / 12.0 + 13.0 * e8 / 360.0)
+            + f64::sin(4.0 * chi) * (7.0 * e4 / 48.0 + 29.0 * e6 / 240.0 + 811.0 * e8 / 11520.0)
+            + f64::sin(6.0 * chi) * (7.0 * e6 / 120.0 + 81.0 * e8 / 1120.0)
+            + f64::sin(8.0 * chi) * (4279.0 * e8 / 161280.0);
+        let lon = c.lon0 - f64::atan2(s * c.gamma0.cos() - v * c.gamma0.sin(), f64::cos(c.b * u_skew / c.a)) / c.b;
+
+        let ll = LatLon {
+            lat: phi.to_degrees(),
+            lon: lon.to_degrees(),
+            ..Default::default()
+        };
+        LatLon { scale: Some(self.forward(&ll).scale), ..ll }
+    }
+
+    fn constants(&self) -> Constants {
+        let e = self.e();
+        let e2 = e * e;
+        let phic = self.latc.to_radians();
+        let alphac = self.azimuth.to_radians();
+        let sinphic = phic.sin();
+
+        let b = f64::sqrt(1.0 + e2 * phic.cos().powi(4) / (1.0 - e2));
+        let a = self.ellipsoid.a() * b * self.k0 * f64::sqrt(1.0 - e2) / (1.0 - e2 * sinphic * sinphic);
+        let d = b * f64::sqrt(1.0 - e2) / (phic.cos() * f64::sqrt(1.0 - e2 * sinphic * sinphic));
+        // D is at least 1 but for rounding
+        let d2 = f64::max(d * d, 1.0);
+        let f = d + f64::sqrt(d2 - 1.0) * sinphic.signum();
+        let h = f * f64::powf(self.t(phic), b);
+        let g = (f - 1.0 / f) / 2.0;
+        let gamma0 = f64::asin(alphac.sin() / d);
+        let lon0 = self.lonc.to_radians() - f64::asin(g * gamma0.tan()) / b;
+
+        // distance along the initial line from the natural origin to the projection centre
+        let uc = if (self.azimuth.abs() - 90.0).abs() < 1e-12 {
+            a * (self.lonc.to_radians() - lon0)
+        } else {
+            a / b * f64::atan(f64::sqrt(d2 - 1.0) / alphac.cos()) * phic.signum()
+        };
+
+        Constants { a, b, h, gamma0, lon0, uc: uc.abs() * phic.signum() }
+    }
+
+    fn t(&self, phi: f64) -> f64 {
+        let e = self.e();
+        let esinphi = e * phi.sin();
+        f64::tan(FRAC_PI_4 - phi / 2.0) / f64::powf((1.0 - esinphi) / (1.0 + esinphi), e / 2.0)
+    }
+
+    fn e(&self) -> f64 {
+        let f = self.ellipsoid.f();
+        f64::sqrt(f * (2.0 - f)) // eccentricity
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HotineObliqueMercator;
+    use ellipsoid::Ellipsoid;
+    use latlon::LatLon;
+
+    #[test]
+    fn centre_at_false_origin() {
+        // LV95, whose initial line runs east-west through Bern
+        let latc = 46.0 + 57.0 / 60.0 + 8.66 / 3600.0;
+        let lonc = 7.0 + 26.0 / 60.0 + 22.50 / 3600.0;
+        let hom = HotineObliqueMercator::variant_b(lonc, latc, 90.0, 90.0, 1.0, 2600000.0, 1200000.0, Ellipsoid::Bessel1841);
+        let centre = hom.forward(&LatLon { lat: latc, lon: lonc, ..Default::default() });
+        assert!((centre.easting - 2600000.0).abs() < 1e-6);
+        assert!((centre.northing - 1200000.0).abs() < 1e-6);
+        assert!((centre.scale - 1.0).abs() < 1e-12);
+
+        let ll = hom.inverse(2700000.0, 1100000.0);
+        let p = hom.forward(&ll);
+        assert!((p.easting - 2700000.0).abs() < 1e-5);
+        assert!((p.northing - 1100000.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn natural_origin() {
+        // SPCS83 Alaska zone 1
+        let azimuth = f64::atan(-0.75).to_degrees();
+        let hom = HotineObliqueMercator::variant_a(-133.0 - 40.0 / 60.0, 57.0, azimuth, azimuth, 0.9999,
+                                                   5000000.0, -5000000.0, Ellipsoid::Grs80);
+        let centre = hom.forward(&LatLon { lat: 57.0, lon: -133.0 - 40.0 / 60.0, ..Default::default() });
+        assert!((centre.easting - 818676.73).abs() < 0.01);
+        assert!((centre.northing - 575097.69).abs() < 0.01);
+
+        let p = hom.forward(&LatLon::new(58.3019, -134.4197).unwrap());
+        let ll = hom.inverse(p.easting, p.northing);
+        assert!((ll.lat - 58.3019).abs() < 1e-9);
+        assert!((ll.lon - -134.4197).abs() < 1e-9);
+    }
+}
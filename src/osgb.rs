@@ -0,0 +1,215 @@
+use std::str::FromStr;
+use std::fmt;
+
+use ascii;
+use Accuracy;
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use ellipsoid::Ellipsoid;
+use parser::OsgbParser;
+use tm::TransverseMercator;
+
+// extent of the grid from its false origin (grid square SV), in metres
+const MAX_EASTING: f64 = 700e3;
+const MAX_NORTHING: f64 = 1300e3;
+
+/// Ordnance Survey National Grid reference (British National Grid) on the OSGB36 datum, such as
+/// `TQ 30080 80500`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Osgb {
+    /// Easting in metres from the false origin
+    pub easting: f64,
+    /// Northing in metres from the false origin
+    pub northing: f64,
+    /// Precision the grid reference was given at
+    pub accuracy: Accuracy,
+}
+
+impl Osgb {
+    pub fn new(easting: f64, northing: f64) -> Result<Self, Errors> {
+        /*!
+        Creates an OS National Grid reference from a numeric easting/northing.
+
+        ### Params
+         * **easting**: Easting in metres from the false origin.
+         * **northing**: Northing in metres from the false origin.
+
+        ### Returns
+         * **Ok**: The grid reference.
+         * **Err**: `Errors::OutsideGrid` if the easting/northing falls outside of the grid.
+        */
+
+        if !(0.0..MAX_EASTING).contains(&easting) || !(0.0..MAX_NORTHING).contains(&northing) {
+            return Err(Errors::OutsideGrid);
+        }
+        Ok(Osgb {
+            easting,
+            northing,
+            accuracy: Accuracy::One,
+        })
+    }
+
+    pub fn projection() -> TransverseMercator {
+        /*!
+        Returns the Transverse Mercator projection the National Grid is defined by.
+        */
+
+        TransverseMercator {
+            lon0: -2.0,
+            lat0: 49.0,
+            k0: 0.9996012717,
+            false_easting: 400e3,
+            false_northing: -100e3,
+            ellipsoid: Ellipsoid::Airy1830,
+        }
+    }
+
+    pub fn from_ll(ll: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to an OS National Grid reference, shifting the point onto the
+        OSGB36 datum first if required.
+
+        ### Returns
+         * **Ok**: The grid reference.
+         * **Err**: `Errors::OutsideGrid` if the point falls outside of the grid.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(51.5080, -0.1281).unwrap();
+        let osgb = Osgb::from_ll(&ll).unwrap(); // osgb.to_string() = 'TQ 30036 80479'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Osgb36);
+        let p = Osgb::projection().forward(&ll);
+        Osgb::new(p.easting, p.northing)
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this grid reference to WGS84 latitude/longitude.
+
+        Use `LatLon::to_datum(Datum::Osgb36)` on the result for OSGB36 latitude/longitude.
+        */
+
+        let mut ll = Osgb::projection().inverse(self.easting, self.northing);
+        ll.datum = Datum::Osgb36;
+        ll.to_datum(Datum::Wgs84)
+    }
+
+    pub fn letters(&self) -> (char, char) {
+        /*!
+        Returns the two-letter 100km grid square this reference falls within, such as `('T', 'Q')`.
+        */
+
+        // get the 100km-grid indices
+        let e100k = f64::floor(self.easting / 100000.0) as i32;
+        let n100k = f64::floor(self.northing / 100000.0) as i32;
+
+        // translate those into numeric equivalents of the grid letters
+        let mut l1 = (19 - n100k) - (19 - n100k) % 5 + (e100k + 10) / 5;
+        let mut l2 = ((19 - n100k) * 5) % 25 + e100k % 5;
+
+        // compensate for skipped 'I' and build grid letter-pairs
+        if l1 > 7 { l1 += 1; }
+        if l2 > 7 { l2 += 1; }
+        ((ascii::A + l1 as u8) as char, (ascii::A + l2 as u8) as char)
+    }
+
+    fn as_string(&self, accuracy: Accuracy) -> String {
+        /*!
+        Returns a string representation of an OS National Grid reference.
+
+        Components are separated by spaces: for an unseparated string, use
+        `osgb.as_string(Accuracy::One).replace(" ", "");`
+
+        ### Params
+         * **accuracy** Precision of returned grid reference (eg `One` = 1m or 10 digit grid,
+           `Ten` = 10m or 8 digit grid, etc.).
+
+        ### Returns
+         * This grid reference in standard format.
+        */
+
+        let (l1, l2) = self.letters();
+
+        let digits: i32 = accuracy.as_num_digits() as i32 / 2;
+        // strip 100km-grid indices from easting & northing, and set required precision
+        let easting = (f64::floor((self.easting % 100000.0) / f64::powi(10.0, 5 - digits))) as usize;
+        let northing = (f64::floor((self.northing % 100000.0) / f64::powi(10.0, 5 - digits))) as usize;
+        if digits == 0 { return format!("{0}{1}", l1, l2); }
+
+        format!("{0}{1} {2:0>4$} {3:0>4$}", l1, l2, easting, northing, digits as usize)
+    }
+}
+
+impl From<Osgb> for LatLon {
+    fn from(osgb: Osgb) -> Self {
+        osgb.to_ll()
+    }
+}
+
+impl FromStr for Osgb {
+    type Err = Errors;
+    /// Parses an OS National Grid reference, either as a letter-pair and digits (`TQ 30080 80500`,
+    /// `TQ3008080500`), or as a fully numeric easting and northing (`530080,180500`).
+    fn from_str(osgb: &str) -> Result<Self, Self::Err> {
+        OsgbParser::new(osgb.as_bytes()).parse()
+    }
+}
+
+impl fmt::Display for Osgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(self.accuracy))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Osgb;
+    use latlon::LatLon;
+    use datum::Datum;
+
+    #[test]
+    fn ordnance_survey_worked_example() {
+        // 'A guide to coordinate systems in Great Britain', section C.1
+        let ll = LatLon {
+            lat: 52.0 + 39.0 / 60.0 + 27.2531 / 3600.0,
+            lon: 1.0 + 43.0 / 60.0 + 4.5177 / 3600.0,
+            datum: Datum::Osgb36,
+            ..Default::default()
+        };
+        let osgb = Osgb::from_ll(&ll).unwrap();
+        assert!((osgb.easting - 651409.903).abs() < 0.001);
+        assert!((osgb.northing - 313177.270).abs() < 0.001);
+        assert_eq!(&*osgb.to_string(), "TG 51409 13177");
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let osgb: Osgb = "TQ 30080 80500".parse().unwrap();
+        assert_eq!(osgb.easting, 530080.0);
+        assert_eq!(osgb.northing, 180500.0);
+        assert_eq!(&*osgb.to_string(), "TQ 30080 80500");
+
+        let osgb: Osgb = "SV0000".parse().unwrap();
+        assert_eq!(osgb.easting, 0.0);
+        assert_eq!(osgb.northing, 0.0);
+
+        let osgb: Osgb = "530080,180500".parse().unwrap();
+        assert_eq!(&*osgb.to_string(), "TQ 30080 80500");
+
+        assert!("TI 123 456".parse::<Osgb>().is_err());
+        assert!("TQ 123 45".parse::<Osgb>().is_err());
+    }
+
+    #[test]
+    fn round_trip_wgs84() {
+        let ll = LatLon::new(51.5080, -0.1281).unwrap();
+        let back = Osgb::from_ll(&ll).unwrap().to_ll();
+        assert!((back.lat - ll.lat).abs() < 1e-6);
+        assert!((back.lon - ll.lon).abs() < 1e-6);
+    }
+}
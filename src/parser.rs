@@ -1,11 +1,18 @@
 use std::str;
-use get_100k_set_for_zone;
 use band::LatBand;
 use col::ColLetter;
 use row::RowLetter;
 
+use ascii;
 use Accuracy;
+use Errors;
 use Mgrs;
+use Osgb;
+use IrishGrid;
+use Itm;
+use swiss::{Lv03, Lv95};
+use gzd::{Gzd, GridSquareId100k};
+use usng::UsngRef;
 
 #[derive(Debug)]
 pub struct MgrsParser<'a> {
@@ -17,7 +24,7 @@ pub struct MgrsParser<'a> {
 impl<'a> MgrsParser<'a> {
     pub fn new(mgrs: &'a [u8]) -> Self {
         MgrsParser {
-            mgrs: mgrs,
+            mgrs,
             pos: 0,
             start: 0,
         }
@@ -48,33 +55,28 @@ impl<'a> MgrsParser<'a> {
 
     fn zone_num(&mut self, mgrs: &mut Mgrs) {
         self.start = self.pos;
-        self.pos += 2;
-        // returns true for non-numeric bytes
-        let s_num = if !numeric(self.mgrs[self.pos]) {
-            unsafe { str::from_utf8_unchecked(&self.mgrs[self.start..self.pos]) }
-        } else {
-            self.pos -= 1;
-            unsafe { str::from_utf8_unchecked(&self.mgrs[self.start..self.pos]) }
-        };
+        // zone numbers are one or two digits (returns true for non-numeric bytes)
+        self.pos += if numeric(self.mgrs[self.start + 1]) { 1 } else { 2 };
+        let s_num = unsafe { str::from_utf8_unchecked(&self.mgrs[self.start..self.pos]) };
         mgrs.gzd.zone = s_num.parse().expect("Failed to parse bytes to number in MGRS string");
     }
 
     fn zone_letter(&mut self, mgrs: &mut Mgrs) {
-        self.pos += 1;
         let c = self.mgrs[self.pos] as char;
         mgrs.gzd.band = LatBand::from(c);
+        self.pos += 1;
     }
 
     fn col_letter(&mut self, mgrs: &mut Mgrs) {
-        self.pos += 1;
         let c = self.mgrs[self.pos] as char;
         mgrs.gsid_100k.col = ColLetter::from(c);
+        self.pos += 1;
     }
 
     fn row_letter(&mut self, mgrs: &mut Mgrs) {
-        self.pos += 1;
         let c = self.mgrs[self.pos] as char;
         mgrs.gsid_100k.row = RowLetter::from(c);
+        self.pos += 1;
     }
 
     fn location(&mut self, mgrs: &mut Mgrs) {
@@ -82,69 +84,386 @@ impl<'a> MgrsParser<'a> {
         let loc = &self.mgrs[self.start..self.mgrs.len()];
 
         let (e, n) = if !contains_whitespace(loc) {
-            assert!(loc.len() % 2 == 0, "Odd number of digits for MGRS grid");
+            assert!(loc.len().is_multiple_of(2), "Odd number of digits for MGRS grid");
             let e = &loc[..loc.len()/2];
             let n = &loc[loc.len()/2..];
             (e, n)
         } else {
-            self.stop_at(whitespace);
-            self.pos += 1;
+            self.stop_at(|b| !whitespace(b));
             let e = &self.mgrs[self.start..self.pos];
-            self.start = self.pos;
             self.stop_at(numeric);
-            let n = &self.mgrs[self.start..];
+            self.start = self.pos;
+            self.stop_at(|b| !whitespace(b));
+            let n = &self.mgrs[self.start..self.pos];
             (e, n)
         };
 
-        mgrs.accuracy = Accuracy::from_num_digits(loc.len()).expect("Failed to retrieve accuracy");
+        mgrs.accuracy = Accuracy::from_num_digits(e.len() + n.len()).expect("Failed to retrieve accuracy");
+        // the 100km square alone, such as `31U DQ`
+        if e.is_empty() { return; }
+
+        let accuracy_bonus: f64 = f64::powi(10.0, 5 - e.len() as i32);
+        let e_str = unsafe { str::from_utf8_unchecked(e) };
+        let n_str = unsafe { str::from_utf8_unchecked(n) };
+        let ef = e_str.parse::<f64>().expect("failed to parse easting in MGRS string") * accuracy_bonus;
+        let nf = n_str.parse::<f64>().expect("failed to parse northing in MGRS string") * accuracy_bonus;
 
-        let set = get_100k_set_for_zone(mgrs.gzd.zone as usize);
+        // easting/northing are kept relative to the 100km grid square, the 100km letters and band
+        // resolve the rest when converting to UTM
+        mgrs.easting = ef as usize;
+        mgrs.northing = nf as usize;
+    }
+}
 
-        let e_100k = mgrs.gsid_100k.col.get_easting_with_set(set as u8);
-        let mut n_100k = mgrs.gsid_100k.row.get_northing_with_set(set as u8);
+/// Cursor over a grid reference written as grid square letters followed by an easting and
+/// northing within the square, shared by the parsers of lettered grids
+#[derive(Debug)]
+struct LetteredRef<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    start: usize,
+}
 
-        // We have a bug where the northing may be 2000000 too low.
-        // How do we know when to roll over?
+impl<'a> LetteredRef<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        LetteredRef {
+            bytes,
+            pos: 0,
+            start: 0,
+        }
+    }
 
-        let min_n = mgrs.gzd.band.get_min_northing().expect("faild to get min northing");
-        while n_100k < min_n {
-            n_100k += 2000000.0;
+    fn stop_at<F>(&mut self, f: F) where F: Fn(u8) -> bool {
+        self.start = self.pos;
+        for b in &self.bytes[self.start..] {
+            if f(*b) { self.pos += 1; continue; }
+            return;
         }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
 
-        let base: usize = 10;
-        let accuracy_bonus: f64 = 100000.0 / base.pow(mgrs.accuracy.as_num_digits() as u32) as f64;
+    fn location(&mut self) -> Result<(Accuracy, f64, f64), Errors> {
+        /*!
+        Parses the digits following the grid square letters.
+
+        ### Returns
+         * **Ok**: The precision the reference was given at, and the easting and northing in
+           metres within the grid square.
+         * **Err**: `Errors::InvalidGridReference` if the easting and northing are missing, of
+           unequal length or longer than 5 digits each.
+        */
+
+        self.stop_at(whitespace);
+        let (e, n) = match easting_northing(self.rest()) {
+            Some(en) => en,
+            None => return Err(self.invalid()),
+        };
+        if e.len() != n.len() || e.is_empty() || e.len() > 5 { return Err(self.invalid()); }
+        self.pos = self.bytes.len();
+
+        let accuracy = Accuracy::from_num_digits(e.len() * 2).ok_or_else(|| self.invalid())?;
+        let accuracy_bonus: f64 = f64::powi(10.0, 5 - e.len() as i32);
         let e_str = unsafe { str::from_utf8_unchecked(e) };
         let n_str = unsafe { str::from_utf8_unchecked(n) };
-        let ef = e_str.parse::<f64>().expect("failed to parse easting in MGRS string") * accuracy_bonus;
-        let nf = n_str.parse::<f64>().expect("failed to parse northing in MGRS string") * accuracy_bonus;
+        let ef = e_str.parse::<f64>().expect("failed to parse easting in grid reference") * accuracy_bonus;
+        let nf = n_str.parse::<f64>().expect("failed to parse northing in grid reference") * accuracy_bonus;
+        Ok((accuracy, ef, nf))
+    }
+
+    fn invalid(&self) -> Errors {
+        Errors::InvalidGridReference(String::from_utf8_lossy(self.bytes).into_owned())
+    }
+}
+
+#[derive(Debug)]
+pub struct OsgbParser<'a> {
+    osgb: LetteredRef<'a>,
+}
+
+impl<'a> OsgbParser<'a> {
+    pub fn new(osgb: &'a [u8]) -> Self {
+        OsgbParser {
+            osgb: LetteredRef::new(osgb),
+        }
+    }
+
+    pub fn parse(mut self) -> Result<Osgb, Errors> {
+        self.osgb.stop_at(whitespace);
+        if self.osgb.at_end() { return Err(self.osgb.invalid()); }
 
-        mgrs.easting = ef + e_100k;
-        mgrs.northing = nf + n_100k;
+        // fully numeric references such as `530080,180500` carry metres directly
+        if !numeric(self.osgb.rest()[0]) {
+            return match metres(self.osgb.rest()) {
+                Some((e, n)) => Osgb::new(e, n),
+                None => Err(self.osgb.invalid()),
+            };
+        }
+
+        let (e100k, n100k) = self.letters()?;
+        let (accuracy, ef, nf) = self.osgb.location()?;
+        let mut osgb = Osgb::new(e100k * 100000.0 + ef, n100k * 100000.0 + nf)?;
+        osgb.accuracy = accuracy;
+        Ok(osgb)
+    }
+
+    fn letters(&mut self) -> Result<(f64, f64), Errors> {
+        let rest = self.osgb.rest();
+        if rest.len() < 2 { return Err(self.osgb.invalid()); }
+        let c1 = rest[0].to_ascii_uppercase();
+        let c2 = rest[1].to_ascii_uppercase();
+        if !is_osgb_letter(c1) || !is_osgb_letter(c2) { return Err(self.osgb.invalid()); }
+        self.osgb.pos += 2;
+
+        // get numeric values of letter references, mapping A->0, B->1, C->2, etc, and shuffling
+        // down letters after 'I' since 'I' is not used in the grid
+        let mut l1 = (c1 - ascii::A) as i32;
+        let mut l2 = (c2 - ascii::A) as i32;
+        if l1 > 7 { l1 -= 1; }
+        if l2 > 7 { l2 -= 1; }
+
+        // convert grid letters into 100km-square indexes from false origin (grid square SV)
+        let e100k = ((l1 - 2) % 5) * 5 + (l2 % 5);
+        let n100k = (19 - (l1 / 5) * 5) - (l2 / 5);
+        if !(0..=6).contains(&e100k) || !(0..=12).contains(&n100k) { return Err(self.osgb.invalid()); }
+        Ok((e100k as f64, n100k as f64))
+    }
+}
+
+#[derive(Debug)]
+pub struct IrishGridParser<'a> {
+    irish: LetteredRef<'a>,
+}
+
+impl<'a> IrishGridParser<'a> {
+    pub fn new(irish: &'a [u8]) -> Self {
+        IrishGridParser {
+            irish: LetteredRef::new(irish),
+        }
+    }
+
+    pub fn parse(mut self) -> Result<IrishGrid, Errors> {
+        self.irish.stop_at(whitespace);
+        if self.irish.at_end() { return Err(self.irish.invalid()); }
+
+        // fully numeric references such as `315904,234671` carry metres directly
+        if !numeric(self.irish.rest()[0]) {
+            return match metres(self.irish.rest()) {
+                Some((e, n)) => IrishGrid::new(e, n),
+                None => Err(self.irish.invalid()),
+            };
+        }
+
+        let (e100k, n100k) = self.letter()?;
+        let (accuracy, ef, nf) = self.irish.location()?;
+        let mut irish = IrishGrid::new(e100k * 100000.0 + ef, n100k * 100000.0 + nf)?;
+        irish.accuracy = accuracy;
+        Ok(irish)
+    }
+
+    fn letter(&mut self) -> Result<(f64, f64), Errors> {
+        let c = self.irish.rest()[0].to_ascii_uppercase();
+        if !is_osgb_letter(c) { return Err(self.irish.invalid()); }
+        self.irish.pos += 1;
+
+        // letters run A-Z (skipping 'I') in rows of 5, west to east, from the north-west corner
+        let mut l = (c - ascii::A) as i32;
+        if l > 7 { l -= 1; }
+        Ok(((l % 5) as f64, (4 - l / 5) as f64))
+    }
+}
+
+#[derive(Debug)]
+pub struct ItmParser<'a> {
+    itm: &'a [u8],
+}
+
+impl<'a> ItmParser<'a> {
+    pub fn new(itm: &'a [u8]) -> Self {
+        ItmParser {
+            itm,
+        }
+    }
+
+    pub fn parse(self) -> Result<Itm, Errors> {
+        match metres(self.itm) {
+            Some((e, n)) => Ok(Itm::new(e, n)),
+            None => Err(Errors::InvalidGridReference(String::from_utf8_lossy(self.itm).into_owned())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Lv03Parser<'a> {
+    lv03: &'a [u8],
+}
+
+impl<'a> Lv03Parser<'a> {
+    pub fn new(lv03: &'a [u8]) -> Self {
+        Lv03Parser {
+            lv03,
+        }
+    }
+
+    pub fn parse(self) -> Result<Lv03, Errors> {
+        match swiss_metres(self.lv03) {
+            Some((e, n)) => Lv03::new(e, n),
+            None => Err(Errors::InvalidGridReference(String::from_utf8_lossy(self.lv03).into_owned())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Lv95Parser<'a> {
+    lv95: &'a [u8],
+}
+
+impl<'a> Lv95Parser<'a> {
+    pub fn new(lv95: &'a [u8]) -> Self {
+        Lv95Parser {
+            lv95,
+        }
+    }
+
+    pub fn parse(self) -> Result<Lv95, Errors> {
+        match swiss_metres(self.lv95) {
+            Some((e, n)) => Lv95::new(e, n),
+            None => Err(Errors::InvalidGridReference(String::from_utf8_lossy(self.lv95).into_owned())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UsngParser<'a> {
+    usng: LetteredRef<'a>,
+}
+
+impl<'a> UsngParser<'a> {
+    pub fn new(usng: &'a [u8]) -> Self {
+        UsngParser {
+            usng: LetteredRef::new(usng),
+        }
+    }
+
+    pub fn parse(mut self) -> Result<UsngRef, Errors> {
+        let mut usng = UsngRef::default();
+        self.usng.stop_at(whitespace);
+        if self.usng.at_end() { return Err(self.usng.invalid()); }
+
+        // the grid zone designator is 1-2 digits directly followed by the band letter; anything
+        // else starting with a digit is a truncated reference of digits only
+        let rest = self.usng.rest();
+        let digits = rest.iter().take_while(|b| !numeric(**b)).count();
+        let letter_follows = rest.get(digits).is_some_and(|b| !whitespace(*b) && numeric(*b));
+        if (digits == 1 || digits == 2) && letter_follows {
+            usng.gzd = Some(self.gzd(digits)?);
+            self.usng.stop_at(whitespace);
+        }
+
+        if !self.usng.at_end() && numeric(self.usng.rest()[0]) {
+            usng.gsid_100k = Some(self.gsid_100k()?);
+        } else if usng.gzd.is_some() {
+            // a grid zone designator can only be followed by the 100km square
+            return Err(self.usng.invalid());
+        }
+
+        let (accuracy, ef, nf) = self.usng.location()?;
+        usng.accuracy = accuracy;
+        usng.easting = ef as usize;
+        usng.northing = nf as usize;
+        Ok(usng)
+    }
+
+    fn gzd(&mut self, digits: usize) -> Result<Gzd, Errors> {
+        let rest = self.usng.rest();
+        let s_num = unsafe { str::from_utf8_unchecked(&rest[..digits]) };
+        let zone: u8 = s_num.parse().expect("Failed to parse bytes to number in USNG string");
+        if !(1..=60).contains(&zone) { return Err(Errors::InvalidZone(zone)); }
+
+        let c = rest[digits].to_ascii_uppercase();
+        if zone_letter(c) { return Err(Errors::InvalidLatitudeBand(c as char)); }
+        self.usng.pos += digits + 1;
+        Ok(Gzd { zone, band: LatBand::from(c as char) })
+    }
+
+    fn gsid_100k(&mut self) -> Result<GridSquareId100k, Errors> {
+        let rest = self.usng.rest();
+        if rest.len() < 2 { return Err(self.usng.invalid()); }
+        let c1 = rest[0].to_ascii_uppercase();
+        let c2 = rest[1].to_ascii_uppercase();
+        if col_letter(c1) { return Err(Errors::InvalidColLetter(c1 as char)); }
+        if row_letter(c2) { return Err(Errors::InvalidRowLetter(c2 as char)); }
+        self.usng.pos += 2;
+        Ok(GridSquareId100k { col: ColLetter::from(c1 as char), row: RowLetter::from(c2 as char) })
+    }
+}
+
+/// Splits a fully numeric easting and northing in metres, separated by a comma and/or whitespace
+fn metres(loc: &[u8]) -> Option<(f64, f64)> {
+    let loc = unsafe { str::from_utf8_unchecked(loc) };
+    let mut parts = loc.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty());
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(e), Some(n), None) => match (e.parse::<f64>(), n.parse::<f64>()) {
+            (Ok(e), Ok(n)) => Some((e, n)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parses an easting and northing as `metres` does, also allowing the Swiss `'` thousands separator
+fn swiss_metres(loc: &[u8]) -> Option<(f64, f64)> {
+    let loc: Vec<u8> = loc.iter().cloned().filter(|b| *b != b'\'').collect();
+    metres(&loc)
+}
+
+/// Splits the digits of a grid reference into its easting and northing, which are either
+/// separated by whitespace or run together with an equal number of digits each
+fn easting_northing(loc: &[u8]) -> Option<(&[u8], &[u8])> {
+    let end = loc.iter().rposition(|b| !whitespace(*b)).map(|i| i + 1).unwrap_or(0);
+    let loc = &loc[..end];
+    if loc.iter().any(|b| numeric(*b) && !whitespace(*b)) { return None; }
+
+    if !contains_whitespace(loc) {
+        if !loc.len().is_multiple_of(2) { return None; }
+        return Some((&loc[..loc.len()/2], &loc[loc.len()/2..]));
+    }
+
+    let mut parts = loc.split(|b| whitespace(*b)).filter(|p| !p.is_empty());
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(e), Some(n), None) => Some((e, n)),
+        _ => None,
     }
 }
 
 #[inline]
 fn numeric(b: u8) -> bool {
     // 48=0, 57=9
-    b < 48 || b > 57
+    !(48..=57).contains(&b)
 }
 
 #[inline]
 fn zone_letter(b: u8) -> bool {
     // C-X, except I and O
-    b < 67 || b > 120 || (b > 88 && b < 99) || exempt_letters(b)
+    !(67..=120).contains(&b) || (b > 88 && b < 99) || exempt_letters(b)
 }
 
 #[inline]
 fn col_letter(b: u8) -> bool {
     // A-Z, except I and O
-    b < 65 || b > 122 || (b > 90 && b < 97) || exempt_letters(b)
+    !(65..=122).contains(&b) || (b > 90 && b < 97) || exempt_letters(b)
 }
 
 #[inline]
 fn row_letter(b: u8) -> bool {
     // A-V, except I and O
-    b < 65 || b > 118 || (b > 86 && b < 97) || exempt_letters(b)
+    !(65..=118).contains(&b) || (b > 86 && b < 97) || exempt_letters(b)
 }
 
 #[inline]
@@ -152,6 +471,12 @@ fn exempt_letters(b: u8) -> bool {
     b == b'I' || b == b'O' || b == b'o' || b == b'i'
 }
 
+#[inline]
+fn is_osgb_letter(b: u8) -> bool {
+    // A-Z, except I
+    (ascii::A..=ascii::Z).contains(&b) && b != ascii::I
+}
+
 #[inline]
 fn whitespace(b: u8) -> bool {
     b < 33
@@ -0,0 +1,367 @@
+use std::str::FromStr;
+use std::fmt;
+
+use Accuracy;
+use Errors;
+use Mgrs;
+use utm::Utm;
+use latlon::LatLon;
+use datum::Datum;
+
+// digits of the code, chosen to avoid spelling words
+const ALPHABET: &[u8; 20] = b"23456789CFGHJMPQRVWX";
+const SEPARATOR: u8 = b'+';
+const PADDING: u8 = b'0';
+// position of the separator in a full code
+const SEPARATOR_POSITION: usize = 8;
+// digits encoding latitude and longitude in pairs, from 20° down to 1/8000°
+const PAIR_CODE_LENGTH: usize = 10;
+// further digits each splitting the cell into 5 rows and 4 columns
+const GRID_CODE_LENGTH: usize = 5;
+const MAX_CODE_LENGTH: usize = PAIR_CODE_LENGTH + GRID_CODE_LENGTH;
+const GRID_ROWS: i64 = 5;
+const GRID_COLUMNS: i64 = 4;
+// integer steps per degree at the longest code
+const LAT_MULTIPLIER: i64 = 8000 * 3125;
+const LON_MULTIPLIER: i64 = 8000 * 1024;
+
+/// Open Location Code, also known as a Plus Code, such as `8FVC9G8F+6X`
+///
+/// Full codes are 2 to 15 digits with a `+` after the eighth, codes shorter than eight digits
+/// being padded with `0`. Codes for places near a known location can be shortened by leaving off
+/// leading digits, see `PlusCode::shorten` and `PlusCode::from_str_near`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlusCode {
+    code: String,
+}
+
+impl PlusCode {
+    pub fn encode(ll: &LatLon, len: usize) -> Self {
+        /*!
+        Encodes a WGS84 latitude/longitude as a full Plus Code.
+
+        ### Params
+         * **ll**: The point to encode.
+         * **len**: Number of digits, 2, 4, 6, 8, or 10..15 inclusive.
+
+        # Panics
+
+        If `len` is not one of 2, 4, 6, 8 or 10..15.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(47.365590, 8.524997).unwrap();
+        let code = PlusCode::encode(&ll, 10); // code.to_string() = '8FVC9G8F+6X'
+        ```
+        */
+
+        if !PlusCode::valid_len(len) { panic!("Invalid Plus Code length {}", len); }
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        // count from the south-west corner of the world, keeping the north pole in the top cell
+        let mut lat = f64::floor((ll.lat + 90.0) * LAT_MULTIPLIER as f64) as i64;
+        lat = lat.clamp(0, 180 * LAT_MULTIPLIER - 1);
+        let mut lon = f64::floor((ll.lon + 180.0).rem_euclid(360.0) * LON_MULTIPLIER as f64) as i64;
+        lon = lon.min(360 * LON_MULTIPLIER - 1);
+
+        // digits are worked out from the least significant
+        let mut digits = Vec::with_capacity(MAX_CODE_LENGTH);
+        for _ in 0..GRID_CODE_LENGTH {
+            let row = lat % GRID_ROWS;
+            let col = lon % GRID_COLUMNS;
+            digits.push(ALPHABET[(row * GRID_COLUMNS + col) as usize]);
+            lat /= GRID_ROWS;
+            lon /= GRID_COLUMNS;
+        }
+        for _ in 0..PAIR_CODE_LENGTH / 2 {
+            digits.push(ALPHABET[(lon % 20) as usize]);
+            digits.push(ALPHABET[(lat % 20) as usize]);
+            lat /= 20;
+            lon /= 20;
+        }
+        digits.reverse();
+        digits.truncate(len);
+        while digits.len() < SEPARATOR_POSITION { digits.push(PADDING); }
+        digits.insert(SEPARATOR_POSITION, SEPARATOR);
+
+        PlusCode { code: digits.into_iter().map(|b| b as char).collect() }
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Returns the cell of this code.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values.
+        */
+
+        let (mut lat, mut lon) = (0.0, 0.0);
+        let (mut height, mut width) = (400.0, 400.0);
+        for (i, b) in self.digits().enumerate() {
+            let idx = PlusCode::index(b) as f64;
+            if i < PAIR_CODE_LENGTH {
+                if i % 2 == 0 {
+                    height /= 20.0;
+                    lat += idx * height;
+                } else {
+                    width /= 20.0;
+                    lon += idx * width;
+                }
+            } else {
+                height /= GRID_ROWS as f64;
+                width /= GRID_COLUMNS as f64;
+                lat += f64::floor(idx / GRID_COLUMNS as f64) * height;
+                lon += (idx % GRID_COLUMNS as f64) * width;
+            }
+        }
+
+        [LatLon { lat: lat - 90.0, lon: lon - 180.0, ..Default::default() },
+         LatLon { lat: f64::min(lat + height - 90.0, 90.0), lon: lon + width - 180.0, ..Default::default() }]
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Decodes this code to the center of its cell.
+        */
+
+        let rect = self.to_ll_rect();
+        LatLon {
+            lat: (rect[0].lat + rect[1].lat) / 2.0,
+            lon: (rect[0].lon + rect[1].lon) / 2.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        /*!
+        Returns the number of digits of this code, leaving out the separator and any padding.
+        */
+
+        self.digits().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digits().next().is_none()
+    }
+
+    pub fn accuracy(&self) -> Accuracy {
+        /*!
+        Returns the MGRS `Accuracy` whose cells are of about the size of this code's cell. See
+        `Accuracy::from_plus_code_len`.
+        */
+
+        Accuracy::from_plus_code_len(self.len())
+    }
+
+    pub fn to_mgrs(&self) -> Mgrs {
+        /*!
+        Converts the center of this code's cell to an MGRS grid reference, at an `Accuracy` of
+        about the size of the cell.
+
+        # Examples
+
+        ```
+        let code: PlusCode = "8FVC9G8F+6X".parse().unwrap();
+        let mgrs = code.to_mgrs(); // mgrs.to_string() = '32T MT 6413 4589'
+        ```
+        */
+
+        Utm::from_ll(&self.to_ll()).to_mgrs(self.accuracy())
+    }
+
+    pub fn shorten(&self, reference: &LatLon) -> String {
+        /*!
+        Returns this code with as many leading digits left off as can be recovered from a nearby
+        reference location, see `PlusCode::from_str_near`.
+
+        ### Params
+         * **reference**: A location known to whoever the code is given to, such as the center of
+           the town it lies in. Digits are only left off while the reference lies well within the
+           area they cover.
+
+        ### Returns
+         * The shortened code, or the full code if it is padded or the reference is too far away.
+
+        # Examples
+
+        ```
+        let code: PlusCode = "9C3W9QCJ+2VX".parse().unwrap();
+        let reference = LatLon::new(51.3701125, -1.217765625).unwrap();
+        let short = code.shorten(&reference); // short = '+2VX'
+        ```
+        */
+
+        if self.len() < SEPARATOR_POSITION { return self.code.clone(); }
+
+        let centre = self.to_ll();
+        let reference = reference.to_datum(Datum::Wgs84);
+        let lat_diff = (centre.lat - reference.lat).abs();
+        let lon_diff = ((centre.lon - reference.lon + 180.0).rem_euclid(360.0) - 180.0).abs();
+        let range = lat_diff.max(lon_diff);
+
+        // leave off 8, 6 or 4 digits while the reference is within 30% of the cell they encode,
+        // so the cell recovered from it can't be a neighbour
+        for &(trim, resolution) in &[(8, 0.0025), (6, 0.05), (4, 1.0)] {
+            if range < resolution * 0.3 {
+                return self.code[trim..].to_owned();
+            }
+        }
+        self.code.clone()
+    }
+
+    pub fn from_str_near(code: &str, reference: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Parses a Plus Code which may be shortened, recovering any left off digits from those of the
+        cell nearest `reference`.
+
+        ### Params
+         * **code**: The full or short code, such as `9QCJ+2VX` or `CJ+2VX`.
+         * **reference**: A location within about 40km of a code shortened to 4 digits before the
+           separator, or 2km of one shortened to 2.
+
+        ### Returns
+         * **Ok**: The full code.
+         * **Err**: `Errors::InvalidGridReference` if `code` is neither a full nor a short code.
+
+        # Examples
+
+        ```
+        let reference = LatLon::new(51.3708675, -1.217765625).unwrap();
+        let code = PlusCode::from_str_near("CJ+2VX", &reference).unwrap();
+        // code.to_string() = '9C3W9QCJ+2VX'
+        ```
+        */
+
+        let short = match code.parse::<PlusCode>() {
+            Err(Errors::TruncatedReference) => code.trim().to_ascii_uppercase(),
+            result => return result,
+        };
+
+        // the short code replaces the last digits of the reference's code
+        let missing = SEPARATOR_POSITION - short.find('+').expect("short Plus Code without separator");
+        let resolution = 20f64.powi(2 - missing as i32 / 2);
+        let reference = reference.to_datum(Datum::Wgs84);
+        let prefix = PlusCode::encode(&reference, MAX_CODE_LENGTH).code[..missing].to_owned();
+        let code = PlusCode { code: prefix + &short };
+
+        // move to the neighbouring cell if that puts the code nearer the reference
+        let mut centre = code.to_ll();
+        if reference.lat + resolution / 2.0 < centre.lat && centre.lat - resolution >= -90.0 {
+            centre.lat -= resolution;
+        } else if reference.lat - resolution / 2.0 > centre.lat && centre.lat + resolution <= 90.0 {
+            centre.lat += resolution;
+        }
+        if reference.lon + resolution / 2.0 < centre.lon {
+            centre.lon -= resolution;
+        } else if reference.lon - resolution / 2.0 > centre.lon {
+            centre.lon += resolution;
+        }
+        Ok(PlusCode::encode(&centre, code.len()))
+    }
+
+    fn valid_len(len: usize) -> bool {
+        ((2..=SEPARATOR_POSITION).contains(&len) && len.is_multiple_of(2)) || (PAIR_CODE_LENGTH..=MAX_CODE_LENGTH).contains(&len)
+    }
+
+    fn digits<'a>(&'a self) -> impl Iterator<Item = u8> + 'a {
+        self.code.bytes().filter(|b| *b != SEPARATOR && *b != PADDING)
+    }
+
+    fn index(b: u8) -> u8 {
+        ALPHABET.iter().position(|c| *c == b).expect("Plus Code digit out of alphabet") as u8
+    }
+}
+
+impl From<PlusCode> for LatLon {
+    fn from(code: PlusCode) -> Self {
+        code.to_ll()
+    }
+}
+
+impl FromStr for PlusCode {
+    type Err = Errors;
+    /// Parses a full Plus Code, in either case. Short codes need `PlusCode::from_str_near`.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let invalid = || Errors::InvalidGridReference(code.to_owned());
+        let normalised = code.trim().to_ascii_uppercase();
+        let b = normalised.as_bytes();
+
+        // exactly one separator, after an even number of digits and followed by none or two or more
+        let sep = match normalised.find('+') {
+            Some(i) if i == normalised.rfind('+').unwrap() => i,
+            _ => return Err(invalid()),
+        };
+        let after = b.len() - sep - 1;
+        if sep > SEPARATOR_POSITION || sep % 2 == 1 || after == 1 || sep + after > MAX_CODE_LENGTH {
+            return Err(invalid());
+        }
+        if !b.iter().all(|c| *c == SEPARATOR || *c == PADDING || ALPHABET.contains(c)) {
+            return Err(invalid());
+        }
+
+        // padding only comes in pairs, fills the rest of a full code before the separator, and
+        // ends it
+        if let Some(pad) = normalised.find('0') {
+            if sep < SEPARATOR_POSITION || pad == 0 || pad % 2 == 1 || after > 0 {
+                return Err(invalid());
+            }
+            if !b[pad..sep].iter().all(|c| *c == PADDING) { return Err(invalid()); }
+        }
+
+        if sep < SEPARATOR_POSITION { return Err(Errors::TruncatedReference); }
+        // the first digits are limited to 90°N and 180°E
+        if PlusCode::index(b[0]) >= 9 || PlusCode::index(b[1]) >= 18 { return Err(invalid()); }
+        Ok(PlusCode { code: normalised })
+    }
+}
+
+impl fmt::Display for PlusCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PlusCode;
+    use latlon::LatLon;
+    use Errors;
+
+    #[test]
+    fn encode_and_decode() {
+        let ll = LatLon { lat: 20.3700625, lon: 2.7821875, ..Default::default() };
+        assert_eq!(&*PlusCode::encode(&ll, 10).to_string(), "7FG49QCJ+2V");
+        assert_eq!(&*PlusCode::encode(&ll, 6).to_string(), "7FG49Q00+");
+        let ll = LatLon { lat: 90.0, lon: 1.0, ..Default::default() };
+        assert_eq!(&*PlusCode::encode(&ll, 4).to_string(), "CFX30000+");
+
+        let code: PlusCode = "7fg49qcj+2vx".parse().unwrap();
+        assert_eq!(&*code.to_string(), "7FG49QCJ+2VX");
+        assert_eq!(code.len(), 11);
+        assert!(!code.is_empty());
+        let rect = code.to_ll_rect();
+        assert!((rect[0].lat - 20.3701).abs() < 1e-9);
+        assert!((rect[1].lon - 2.78225).abs() < 1e-9);
+
+        assert!("7FG49QCJ+2".parse::<PlusCode>().is_err());
+        assert!("7FG49Q0+".parse::<PlusCode>().is_err());
+        assert!("WC2345G6+3H".parse::<PlusCode>().is_err());
+        match "CJ+2VX".parse::<PlusCode>() {
+            Err(Errors::TruncatedReference) => {},
+            result => panic!("expected a truncated reference, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn shorten_and_recover() {
+        let code: PlusCode = "9C3W9QCJ+2VX".parse().unwrap();
+        let reference = LatLon { lat: 51.3708675, lon: -1.217765625, ..Default::default() };
+        assert_eq!(&*code.shorten(&reference), "CJ+2VX");
+        assert_eq!(PlusCode::from_str_near("CJ+2VX", &reference).unwrap(), code);
+
+        // the nearest cell lies across the equator and prime meridian from the reference's
+        let origin = LatLon { lat: 0.0, lon: 0.0, ..Default::default() };
+        assert_eq!(&*PlusCode::from_str_near("XXXXXX+XX", &origin).unwrap().to_string(), "6CXXXXXX+XX");
+    }
+}
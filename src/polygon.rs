@@ -0,0 +1,161 @@
+// Planar geometry on latitude/longitude outlines, used to compare cells of different grids.
+//
+// Longitude is treated as x and latitude as y; cells are small enough, and never cross the
+// antimeridian, for this to be adequate.
+
+use latlon::LatLon;
+
+pub fn bounds(points: &[LatLon]) -> [LatLon; 2] {
+    /*!
+    Returns the bounding box of a set of points.
+
+    ### Return
+     * An array of `LatLon` structs which represents bottom-left, and top-right values.
+    */
+
+    let mut rect = [
+        LatLon { lat: 90.0, lon: 180.0, ..Default::default() },
+        LatLon { lat: -90.0, lon: -180.0, ..Default::default() },
+    ];
+    for ll in points {
+        rect[0].lat = rect[0].lat.min(ll.lat);
+        rect[0].lon = rect[0].lon.min(ll.lon);
+        rect[1].lat = rect[1].lat.max(ll.lat);
+        rect[1].lon = rect[1].lon.max(ll.lon);
+    }
+    rect
+}
+
+pub fn clip_to_rect(points: &[LatLon], rect: &[LatLon; 2]) -> Vec<LatLon> {
+    /*!
+    Clips a polygon to a latitude/longitude rectangle (Sutherland-Hodgman).
+
+    ### Params
+     * **points**: Outline of the polygon, which must be convex for an exact result.
+     * **rect**: Bottom-left and top-right of the rectangle.
+
+    ### Return
+     * Outline of the part of the polygon inside the rectangle, empty if they do not overlap.
+    */
+
+    let (s, w, n, e) = (rect[0].lat, rect[0].lon, rect[1].lat, rect[1].lon);
+    let mut out = points.to_vec();
+    out = clip(&out, |p| p.lon >= w, |a, b| at_lon(a, b, w));
+    out = clip(&out, |p| p.lon <= e, |a, b| at_lon(a, b, e));
+    out = clip(&out, |p| p.lat >= s, |a, b| at_lat(a, b, s));
+    out = clip(&out, |p| p.lat <= n, |a, b| at_lat(a, b, n));
+    out
+}
+
+pub fn clip_to_convex(points: &[LatLon], window: &[LatLon]) -> Vec<LatLon> {
+    /*!
+    Clips a polygon to a convex polygon (Sutherland-Hodgman).
+
+    ### Params
+     * **points**: Outline of the polygon to clip.
+     * **window**: Outline of the convex polygon to clip to, anticlockwise.
+
+    ### Return
+     * Outline of the part of the polygon inside the window, empty if they do not overlap.
+    */
+
+    let mut out = points.to_vec();
+    for i in 0..window.len() {
+        let (a, b) = (&window[i], &window[(i + 1) % window.len()]);
+        let side = |p: &LatLon| (b.lon - a.lon) * (p.lat - a.lat) - (b.lat - a.lat) * (p.lon - a.lon);
+        out = clip(&out, |p| side(p) >= 0.0, |p, q| {
+            let t = side(p) / (side(p) - side(q));
+            LatLon { lat: p.lat + t * (q.lat - p.lat), lon: p.lon + t * (q.lon - p.lon), ..*p }
+        });
+        if out.is_empty() { break; }
+    }
+    out
+}
+
+pub fn clip_line_to_rect(points: &[LatLon], rect: &[LatLon; 2]) -> Vec<Vec<LatLon>> {
+    /*!
+    Clips a polyline to a latitude/longitude rectangle (Liang-Barsky).
+
+    ### Params
+     * **points**: Points along the line.
+     * **rect**: Bottom-left and top-right of the rectangle.
+
+    ### Return
+     * The pieces of the line inside the rectangle, in order along the line.
+    */
+
+    let (s, w, n, e) = (rect[0].lat, rect[0].lon, rect[1].lat, rect[1].lon);
+    let mut pieces = Vec::new();
+    let mut piece: Vec<LatLon> = Vec::new();
+    for pair in points.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let (dx, dy) = (b.lon - a.lon, b.lat - a.lat);
+        let (mut t0, mut t1) = (0.0, 1.0);
+        for &(p, q) in &[(-dx, a.lon - w), (dx, e - a.lon), (-dy, a.lat - s), (dy, n - a.lat)] {
+            if p == 0.0 {
+                if q < 0.0 { t1 = -1.0; }
+            } else if p < 0.0 {
+                t0 = f64::max(t0, q / p);
+            } else {
+                t1 = f64::min(t1, q / p);
+            }
+        }
+
+        let at = |t: f64| LatLon { lat: a.lat + t * dy, lon: a.lon + t * dx, ..*a };
+        if t0 > t1 {
+            if piece.len() > 1 { pieces.push(piece); }
+            piece = Vec::new();
+            continue;
+        }
+        if t0 > 0.0 || piece.is_empty() {
+            if piece.len() > 1 { pieces.push(piece); }
+            piece = vec![at(t0)];
+        }
+        piece.push(at(t1));
+        if t1 < 1.0 {
+            pieces.push(piece);
+            piece = Vec::new();
+        }
+    }
+    if piece.len() > 1 { pieces.push(piece); }
+    pieces
+}
+
+pub fn area(points: &[LatLon]) -> f64 {
+    /*!
+    Returns the area of a polygon (shoelace formula), in square degrees.
+    */
+
+    let mut sum = 0.0;
+    for i in 0..points.len() {
+        let (a, b) = (&points[i], &points[(i + 1) % points.len()]);
+        sum += a.lon * b.lat - b.lon * a.lat;
+    }
+    (sum / 2.0).abs()
+}
+
+fn clip<I, X>(points: &[LatLon], inside: I, intersect: X) -> Vec<LatLon>
+    where I: Fn(&LatLon) -> bool,
+          X: Fn(&LatLon, &LatLon) -> LatLon {
+    let mut out = Vec::with_capacity(points.len() + 4);
+    for i in 0..points.len() {
+        let (prev, cur) = (&points[(i + points.len() - 1) % points.len()], &points[i]);
+        match (inside(prev), inside(cur)) {
+            (true, true) => out.push(*cur),
+            (true, false) => out.push(intersect(prev, cur)),
+            (false, true) => { out.push(intersect(prev, cur)); out.push(*cur); },
+            (false, false) => {},
+        }
+    }
+    out
+}
+
+fn at_lon(a: &LatLon, b: &LatLon, lon: f64) -> LatLon {
+    let t = (lon - a.lon) / (b.lon - a.lon);
+    LatLon { lat: a.lat + t * (b.lat - a.lat), lon, ..*a }
+}
+
+fn at_lat(a: &LatLon, b: &LatLon, lat: f64) -> LatLon {
+    let t = (lat - a.lat) / (b.lat - a.lat);
+    LatLon { lat, lon: a.lon + t * (b.lon - a.lon), ..*a }
+}
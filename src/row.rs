@@ -2,14 +2,38 @@ use std::fmt;
 use std::str::FromStr;
 use std::convert::From;
 
-use ascii;
 use Errors;
 
-#[derive(PartialEq, PartialOrd, Debug, Copy, Clone)]
+/// Scheme the 100km square row letters of an MGRS grid are assigned by, see `Datum::lettering`
+///
+/// A parsed `Mgrs` does not know its datum: `Utm::from(Mgrs)` reads its letters as WGS84 with the
+/// AA scheme, so AL lettered references must be converted with `Utm::from_mgrs_with_datum`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Lettering {
+    /// Rows lettered from A (odd zones) or F (even zones) at the equator, used on modern datums
+    #[default]
+    AA,
+    /// Rows lettered from L (odd zones) or R (even zones) at the equator, used on older maps on
+    /// the NAD27, Arc 1960 and Tokyo datums
+    AL,
+}
+
+impl Lettering {
+    fn row_offset(&self) -> u8 {
+        // number of rows the lettering is shifted north by, relative to AA
+        match *self {
+            Lettering::AA => 0,
+            Lettering::AL => 10,
+        }
+    }
+}
+
+#[derive(PartialEq, PartialOrd, Debug, Copy, Clone, Default)]
 /// 100km grid square row letters
 ///
 /// Repeats every other zone with sets: 'ABCDEFGHJKLMNPQRSTUV', 'FGHJKLMNPQRSTUVABCDE'
 pub enum RowLetter {
+    #[default]
     A, B, C, D, E, F, G, H, J, K, L, M, N, P, Q, R, S, T, U, V
 }
 
@@ -19,6 +43,44 @@ impl RowLetter {
         self.index_from_set((zone-1)%2) * 100000
     }
 
+    pub fn as_meters_from_zone(&self, zone: u8) -> usize {
+        // get northing specified by n100k, within its 2,000km block
+        self.index_from_set((zone-1)%2) * 100000
+    }
+
+    pub fn as_meters_with_lettering(&self, zone: u8, lettering: Lettering) -> usize {
+        /*!
+        Returns the northing of this row within its 2,000km block, in the given lettering scheme.
+        */
+
+        let offset = lettering.row_offset() as usize;
+        (self.index_from_set((zone - 1) % 2) + 20 - offset) % 20 * 100000
+    }
+
+    pub fn from_northing_with_lettering(zone: u8, northing: i32, lettering: Lettering) -> Self {
+        /*!
+        Returns the letter of the row containing a northing, in the given lettering scheme.
+
+        # Examples
+
+        ```
+        let aa = RowLetter::from_northing_with_lettering(31, 5411932, Lettering::AA); // aa = Q
+        let al = RowLetter::from_northing_with_lettering(31, 5411932, Lettering::AL); // al = E
+        ```
+        */
+
+        RowLetter::letter_at(zone, (RowLetter::index_for_northing(northing) + lettering.row_offset()) % 20)
+    }
+
+    pub fn relettered(&self, zone: u8, from: Lettering, to: Lettering) -> Self {
+        /*!
+        Converts this row letter from one lettering scheme to another, for the same row.
+        */
+
+        let index = self.index_from_set((zone - 1) % 2) as u8;
+        RowLetter::letter_at(zone, (index + 20 - from.row_offset() + to.row_offset()) % 20)
+    }
+
     pub fn index_for_northing(northing: i32) -> u8 {
         (f64::floor(northing as f64 / 100000.0) as u8) % 20
     }
@@ -38,13 +100,11 @@ impl RowLetter {
                 A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7, J => 8, K => 9,
                 L => 10, M => 11, N => 12, P => 13, Q => 14, R => 15, S => 16, T => 17, U => 18,
                 V => 19,
-                _ => panic!("Invalid n100k letter for set 1")
             },
             1 => match *self {
                 F => 0, G => 1, H => 2, J => 3, K => 4, L => 5, M => 6, N => 7, P => 8, Q => 9,
                 R => 10, S => 11, T => 12, U => 13, V => 14, A => 15, B => 16, C => 17, D => 18,
                 E => 19,
-                _ => panic!("Invalid n100k letter for set 2")
             },
             _ => panic!("Invalid n100k set"),
         }
@@ -117,19 +177,20 @@ impl<'a> From<&'a RowLetter> for char {
 
 impl From<char> for RowLetter {
     fn from(c: char) -> Self {
-        RowLetter::from(c as u32)
+        c.to_string().parse().expect("invalid 100km grid square row letter")
     }
 }
 
-impl Default for RowLetter {
-    fn default() -> Self {
-        RowLetter::A
+impl fmt::Display for RowLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c: char = (*self).into();
+        write!(f, "{}", c)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::RowLetter;
+    use super::{RowLetter, Lettering};
 
     #[test]
     fn from_char() {
@@ -156,12 +217,14 @@ mod test {
     #[test]
     fn from_str() {
         let c = "c";
-        let cl: RowLetter = c.parse();
-        assert_eq!(cl, Ok(RowLetter::C));
+        let cl: RowLetter = c.parse().unwrap();
+        assert_eq!(cl, RowLetter::C);
 
         let c = "C";
-        let cl: RowLetter = c.parse();
-        assert_eq!(cl, Ok(RowLetter::C));
+        let cl: RowLetter = c.parse().unwrap();
+        assert_eq!(cl, RowLetter::C);
+
+        assert!("I".parse::<RowLetter>().is_err());
     }
 
     #[test]
@@ -170,11 +233,22 @@ mod test {
         let c = char::from(cl);
         assert_eq!(c, 'C');
     }
-}
 
-impl fmt::Display for RowLetter {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let c: char = (*self).into();
-        writeln!(f, "{}", c)
+    #[test]
+    fn lettering() {
+        // the rows at the equator
+        assert_eq!(RowLetter::from_northing_with_lettering(31, 0, Lettering::AL), RowLetter::L);
+        assert_eq!(RowLetter::from_northing_with_lettering(32, 0, Lettering::AL), RowLetter::R);
+        assert_eq!(RowLetter::L.as_meters_with_lettering(31, Lettering::AL), 0);
+        assert_eq!(RowLetter::A.as_meters_with_lettering(31, Lettering::AL), 1000000);
+
+        for zone in 31..33 {
+            for i in 0..20 {
+                let northing = i * 100000;
+                let al = RowLetter::from_northing_with_lettering(zone, northing, Lettering::AL);
+                assert_eq!(al.as_meters_with_lettering(zone, Lettering::AL), northing as usize);
+                assert_eq!(al.relettered(zone, Lettering::AL, Lettering::AA), RowLetter::from_zone_and_northing(zone, northing));
+            }
+        }
     }
 }
@@ -0,0 +1,462 @@
+use std::fmt;
+
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use ellipsoid::Ellipsoid;
+use tm::TransverseMercator;
+use lcc::LambertConformalConic;
+use omerc::HotineObliqueMercator;
+
+/// Length of the US survey foot in metres
+const US_SURVEY_FOOT: f64 = 1200.0 / 3937.0;
+
+/// Unit of length State Plane coordinates are given in
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LinearUnit {
+    Metre,
+    /// The US survey foot (1200/3937 m), which most states legislated for SPCS83
+    UsSurveyFoot,
+}
+
+impl LinearUnit {
+    /// Length of the unit in metres
+    pub fn as_metres(&self) -> f64 {
+        match *self {
+            LinearUnit::Metre => 1.0,
+            LinearUnit::UsSurveyFoot => US_SURVEY_FOOT,
+        }
+    }
+}
+
+/// Projection a State Plane zone is defined by
+#[derive(Copy, Clone, Debug)]
+pub enum SpcsProjection {
+    TransverseMercator(TransverseMercator),
+    LambertConformalConic(LambertConformalConic),
+    HotineObliqueMercator(HotineObliqueMercator),
+}
+
+// angles are held as (degrees, minutes), the way the zones are published, with the sign on the
+// degrees
+#[derive(Copy, Clone, Debug)]
+enum ZoneParameters {
+    /// Latitude of origin, central meridian, central scale as the denominator of its reduction
+    /// from unity (1 part in n, 0 for unity), false easting, false northing
+    Tm((i16, u8), (i16, u8), u32, f64, f64),
+    /// Standard parallels, latitude of origin, central meridian, false easting, false northing
+    Lcc((i16, u8), (i16, u8), (i16, u8), (i16, u8), f64, f64),
+    /// Latitude and longitude of the projection centre, azimuth of the initial line (also the
+    /// rectified grid angle) in degrees, central scale as for `Tm`, false easting and northing at
+    /// the natural origin
+    Omerc((i16, u8), (i16, u8), f64, u32, f64, f64),
+}
+
+/// A zone of the NAD83 State Plane Coordinate System (SPCS83)
+#[derive(Copy, Clone, Debug)]
+pub struct StatePlaneZone {
+    /// FIPS zone code, such as `405` for California zone V
+    pub code: u16,
+    pub name: &'static str,
+    parameters: ZoneParameters,
+}
+
+macro_rules! tm {
+    ($code:expr, $name:expr, $lat0:expr, $lon0:expr, $k0:expr, $fe:expr, $fn:expr) => {
+        StatePlaneZone { code: $code, name: $name, parameters: ZoneParameters::Tm($lat0, $lon0, $k0, $fe, $fn) }
+    }
+}
+
+macro_rules! omerc {
+    ($code:expr, $name:expr, $latc:expr, $lonc:expr, $azimuth:expr, $k0:expr, $fe:expr, $fn:expr) => {
+        StatePlaneZone { code: $code, name: $name, parameters: ZoneParameters::Omerc($latc, $lonc, $azimuth, $k0, $fe, $fn) }
+    }
+}
+
+macro_rules! lcc {
+    ($code:expr, $name:expr, $lat1:expr, $lat2:expr, $lat0:expr, $lon0:expr, $fe:expr, $fn:expr) => {
+        StatePlaneZone { code: $code, name: $name, parameters: ZoneParameters::Lcc($lat1, $lat2, $lat0, $lon0, $fe, $fn) }
+    }
+}
+
+// NOAA Manual NOS NGS 5, State Plane Coordinate System of 1983
+static ZONES: [StatePlaneZone; 124] = [
+    tm!(101, "Alabama East", (30, 30), (-85, 50), 25000, 200000.0, 0.0),
+    tm!(102, "Alabama West", (30, 0), (-87, 30), 15000, 600000.0, 0.0),
+    tm!(201, "Arizona East", (31, 0), (-110, 10), 10000, 213360.0, 0.0),
+    tm!(202, "Arizona Central", (31, 0), (-111, 55), 10000, 213360.0, 0.0),
+    tm!(203, "Arizona West", (31, 0), (-113, 45), 15000, 213360.0, 0.0),
+    lcc!(301, "Arkansas North", (34, 56), (36, 14), (34, 20), (-92, 0), 400000.0, 0.0),
+    lcc!(302, "Arkansas South", (33, 18), (34, 46), (32, 40), (-92, 0), 400000.0, 400000.0),
+    lcc!(401, "California I", (40, 0), (41, 40), (39, 20), (-122, 0), 2000000.0, 500000.0),
+    lcc!(402, "California II", (38, 20), (39, 50), (37, 40), (-122, 0), 2000000.0, 500000.0),
+    lcc!(403, "California III", (37, 4), (38, 26), (36, 30), (-120, 30), 2000000.0, 500000.0),
+    lcc!(404, "California IV", (36, 0), (37, 15), (35, 20), (-119, 0), 2000000.0, 500000.0),
+    lcc!(405, "California V", (34, 2), (35, 28), (33, 30), (-118, 0), 2000000.0, 500000.0),
+    lcc!(406, "California VI", (32, 47), (33, 53), (32, 10), (-116, 15), 2000000.0, 500000.0),
+    lcc!(501, "Colorado North", (39, 43), (40, 47), (39, 20), (-105, 30), 914401.8289, 304800.6096),
+    lcc!(502, "Colorado Central", (38, 27), (39, 45), (37, 50), (-105, 30), 914401.8289, 304800.6096),
+    lcc!(503, "Colorado South", (37, 14), (38, 26), (36, 40), (-105, 30), 914401.8289, 304800.6096),
+    lcc!(600, "Connecticut", (41, 12), (41, 52), (40, 50), (-72, 45), 304800.6096, 152400.3048),
+    tm!(700, "Delaware", (38, 0), (-75, 25), 200000, 200000.0, 0.0),
+    tm!(901, "Florida East", (24, 20), (-81, 0), 17000, 200000.0, 0.0),
+    tm!(902, "Florida West", (24, 20), (-82, 0), 17000, 200000.0, 0.0),
+    lcc!(903, "Florida North", (29, 35), (30, 45), (29, 0), (-84, 30), 600000.0, 0.0),
+    tm!(1001, "Georgia East", (30, 0), (-82, 10), 10000, 200000.0, 0.0),
+    tm!(1002, "Georgia West", (30, 0), (-84, 10), 10000, 700000.0, 0.0),
+    tm!(1101, "Idaho East", (41, 40), (-112, 10), 19000, 200000.0, 0.0),
+    tm!(1102, "Idaho Central", (41, 40), (-114, 0), 19000, 500000.0, 0.0),
+    tm!(1103, "Idaho West", (41, 40), (-115, 45), 15000, 800000.0, 0.0),
+    tm!(1201, "Illinois East", (36, 40), (-88, 20), 40000, 300000.0, 0.0),
+    tm!(1202, "Illinois West", (36, 40), (-90, 10), 17000, 700000.0, 0.0),
+    tm!(1301, "Indiana East", (37, 30), (-85, 40), 30000, 100000.0, 250000.0),
+    tm!(1302, "Indiana West", (37, 30), (-87, 5), 30000, 900000.0, 250000.0),
+    lcc!(1401, "Iowa North", (42, 4), (43, 16), (41, 30), (-93, 30), 1500000.0, 1000000.0),
+    lcc!(1402, "Iowa South", (40, 37), (41, 47), (40, 0), (-93, 30), 500000.0, 0.0),
+    lcc!(1501, "Kansas North", (38, 43), (39, 47), (38, 20), (-98, 0), 400000.0, 0.0),
+    lcc!(1502, "Kansas South", (37, 16), (38, 34), (36, 40), (-98, 30), 400000.0, 400000.0),
+    lcc!(1600, "Kentucky Single Zone", (37, 5), (38, 40), (36, 20), (-85, 45), 1500000.0, 1000000.0),
+    lcc!(1601, "Kentucky North", (37, 58), (38, 58), (37, 30), (-84, 15), 500000.0, 0.0),
+    lcc!(1602, "Kentucky South", (36, 44), (37, 56), (36, 20), (-85, 45), 500000.0, 500000.0),
+    lcc!(1701, "Louisiana North", (31, 10), (32, 40), (30, 30), (-92, 30), 1000000.0, 0.0),
+    lcc!(1702, "Louisiana South", (29, 18), (30, 42), (28, 30), (-91, 20), 1000000.0, 0.0),
+    lcc!(1703, "Louisiana Offshore", (26, 10), (27, 50), (25, 30), (-91, 20), 1000000.0, 0.0),
+    tm!(1801, "Maine East", (43, 40), (-68, 30), 10000, 300000.0, 0.0),
+    tm!(1802, "Maine West", (42, 50), (-70, 10), 30000, 900000.0, 0.0),
+    lcc!(1900, "Maryland", (38, 18), (39, 27), (37, 40), (-77, 0), 400000.0, 0.0),
+    lcc!(2001, "Massachusetts Mainland", (41, 43), (42, 41), (41, 0), (-71, 30), 200000.0, 750000.0),
+    lcc!(2002, "Massachusetts Island", (41, 17), (41, 29), (41, 0), (-70, 30), 500000.0, 0.0),
+    lcc!(2111, "Michigan North", (45, 29), (47, 5), (44, 47), (-87, 0), 8000000.0, 0.0),
+    lcc!(2112, "Michigan Central", (44, 11), (45, 42), (43, 19), (-84, 22), 6000000.0, 0.0),
+    lcc!(2113, "Michigan South", (42, 6), (43, 40), (41, 30), (-84, 22), 4000000.0, 0.0),
+    lcc!(2201, "Minnesota North", (47, 2), (48, 38), (46, 30), (-93, 6), 800000.0, 100000.0),
+    lcc!(2202, "Minnesota Central", (45, 37), (47, 3), (45, 0), (-94, 15), 800000.0, 100000.0),
+    lcc!(2203, "Minnesota South", (43, 47), (45, 13), (43, 0), (-94, 0), 800000.0, 100000.0),
+    tm!(2301, "Mississippi East", (29, 30), (-88, 50), 20000, 300000.0, 0.0),
+    tm!(2302, "Mississippi West", (29, 30), (-90, 20), 20000, 700000.0, 0.0),
+    tm!(2401, "Missouri East", (35, 50), (-90, 30), 15000, 250000.0, 0.0),
+    tm!(2402, "Missouri Central", (35, 50), (-92, 30), 15000, 500000.0, 0.0),
+    tm!(2403, "Missouri West", (36, 10), (-94, 30), 17000, 850000.0, 0.0),
+    lcc!(2500, "Montana", (45, 0), (49, 0), (44, 15), (-109, 30), 600000.0, 0.0),
+    lcc!(2600, "Nebraska", (40, 0), (43, 0), (39, 50), (-100, 0), 500000.0, 0.0),
+    tm!(2701, "Nevada East", (34, 45), (-115, 35), 10000, 200000.0, 8000000.0),
+    tm!(2702, "Nevada Central", (34, 45), (-116, 40), 10000, 500000.0, 6000000.0),
+    tm!(2703, "Nevada West", (34, 45), (-118, 35), 10000, 800000.0, 4000000.0),
+    tm!(2800, "New Hampshire", (42, 30), (-71, 40), 30000, 300000.0, 0.0),
+    tm!(2900, "New Jersey", (38, 50), (-74, 30), 10000, 150000.0, 0.0),
+    tm!(3001, "New Mexico East", (31, 0), (-104, 20), 11000, 165000.0, 0.0),
+    tm!(3002, "New Mexico Central", (31, 0), (-106, 15), 10000, 500000.0, 0.0),
+    tm!(3003, "New Mexico West", (31, 0), (-107, 50), 12000, 830000.0, 0.0),
+    tm!(3101, "New York East", (38, 50), (-74, 30), 10000, 150000.0, 0.0),
+    tm!(3102, "New York Central", (40, 0), (-76, 35), 16000, 250000.0, 0.0),
+    tm!(3103, "New York West", (40, 0), (-78, 35), 16000, 350000.0, 0.0),
+    lcc!(3104, "New York Long Island", (40, 40), (41, 2), (40, 10), (-74, 0), 300000.0, 0.0),
+    lcc!(3200, "North Carolina", (34, 20), (36, 10), (33, 45), (-79, 0), 609601.22, 0.0),
+    lcc!(3301, "North Dakota North", (47, 26), (48, 44), (47, 0), (-100, 30), 600000.0, 0.0),
+    lcc!(3302, "North Dakota South", (46, 11), (47, 29), (45, 40), (-100, 30), 600000.0, 0.0),
+    lcc!(3401, "Ohio North", (40, 26), (41, 42), (39, 40), (-82, 30), 600000.0, 0.0),
+    lcc!(3402, "Ohio South", (38, 44), (40, 2), (38, 0), (-82, 30), 600000.0, 0.0),
+    lcc!(3501, "Oklahoma North", (35, 34), (36, 46), (35, 0), (-98, 0), 600000.0, 0.0),
+    lcc!(3502, "Oklahoma South", (33, 56), (35, 14), (33, 20), (-98, 0), 600000.0, 0.0),
+    lcc!(3601, "Oregon North", (44, 20), (46, 0), (43, 40), (-120, 30), 2500000.0, 0.0),
+    lcc!(3602, "Oregon South", (42, 20), (44, 0), (41, 40), (-120, 30), 1500000.0, 0.0),
+    lcc!(3701, "Pennsylvania North", (40, 53), (41, 57), (40, 10), (-77, 45), 600000.0, 0.0),
+    lcc!(3702, "Pennsylvania South", (39, 56), (40, 58), (39, 20), (-77, 45), 600000.0, 0.0),
+    tm!(3800, "Rhode Island", (41, 5), (-71, 30), 160000, 100000.0, 0.0),
+    lcc!(3900, "South Carolina", (32, 30), (34, 50), (31, 50), (-81, 0), 609600.0, 0.0),
+    lcc!(4001, "South Dakota North", (44, 25), (45, 41), (43, 50), (-100, 0), 600000.0, 0.0),
+    lcc!(4002, "South Dakota South", (42, 50), (44, 24), (42, 20), (-100, 20), 600000.0, 0.0),
+    lcc!(4100, "Tennessee", (35, 15), (36, 25), (34, 20), (-86, 0), 600000.0, 0.0),
+    lcc!(4201, "Texas North", (34, 39), (36, 11), (34, 0), (-101, 30), 200000.0, 1000000.0),
+    lcc!(4202, "Texas North Central", (32, 8), (33, 58), (31, 40), (-98, 30), 600000.0, 2000000.0),
+    lcc!(4203, "Texas Central", (30, 7), (31, 53), (29, 40), (-100, 20), 700000.0, 3000000.0),
+    lcc!(4204, "Texas South Central", (28, 23), (30, 17), (27, 50), (-99, 0), 600000.0, 4000000.0),
+    lcc!(4205, "Texas South", (26, 10), (27, 50), (25, 40), (-98, 30), 300000.0, 5000000.0),
+    lcc!(4301, "Utah North", (40, 43), (41, 47), (40, 20), (-111, 30), 500000.0, 1000000.0),
+    lcc!(4302, "Utah Central", (39, 1), (40, 39), (38, 20), (-111, 30), 500000.0, 2000000.0),
+    lcc!(4303, "Utah South", (37, 13), (38, 21), (36, 40), (-111, 30), 500000.0, 3000000.0),
+    tm!(4400, "Vermont", (42, 30), (-72, 30), 28000, 500000.0, 0.0),
+    lcc!(4501, "Virginia North", (38, 2), (39, 12), (37, 40), (-78, 30), 3500000.0, 2000000.0),
+    lcc!(4502, "Virginia South", (36, 46), (37, 58), (36, 20), (-78, 30), 3500000.0, 1000000.0),
+    lcc!(4601, "Washington North", (47, 30), (48, 44), (47, 0), (-120, 50), 500000.0, 0.0),
+    lcc!(4602, "Washington South", (45, 50), (47, 20), (45, 20), (-120, 30), 500000.0, 0.0),
+    lcc!(4701, "West Virginia North", (39, 0), (40, 15), (38, 30), (-79, 30), 600000.0, 0.0),
+    lcc!(4702, "West Virginia South", (37, 29), (38, 53), (37, 0), (-81, 0), 600000.0, 0.0),
+    lcc!(4801, "Wisconsin North", (45, 34), (46, 46), (45, 10), (-90, 0), 600000.0, 0.0),
+    lcc!(4802, "Wisconsin Central", (44, 15), (45, 30), (43, 50), (-90, 0), 600000.0, 0.0),
+    lcc!(4803, "Wisconsin South", (42, 44), (44, 4), (42, 0), (-90, 0), 600000.0, 0.0),
+    tm!(4901, "Wyoming East", (40, 30), (-105, 10), 16000, 200000.0, 0.0),
+    tm!(4902, "Wyoming East Central", (40, 30), (-107, 20), 16000, 400000.0, 100000.0),
+    tm!(4903, "Wyoming West Central", (40, 30), (-108, 45), 16000, 600000.0, 0.0),
+    tm!(4904, "Wyoming West", (40, 30), (-110, 5), 16000, 800000.0, 100000.0),
+    // initial line at an azimuth of arctan(-3/4)
+    omerc!(5001, "Alaska 1", (57, 0), (-133, 40), -36.86989764584402, 10000, 5000000.0, -5000000.0),
+    tm!(5002, "Alaska 2", (54, 0), (-142, 0), 10000, 500000.0, 0.0),
+    tm!(5003, "Alaska 3", (54, 0), (-146, 0), 10000, 500000.0, 0.0),
+    tm!(5004, "Alaska 4", (54, 0), (-150, 0), 10000, 500000.0, 0.0),
+    tm!(5005, "Alaska 5", (54, 0), (-154, 0), 10000, 500000.0, 0.0),
+    tm!(5006, "Alaska 6", (54, 0), (-158, 0), 10000, 500000.0, 0.0),
+    tm!(5007, "Alaska 7", (54, 0), (-162, 0), 10000, 500000.0, 0.0),
+    tm!(5008, "Alaska 8", (54, 0), (-166, 0), 10000, 500000.0, 0.0),
+    tm!(5009, "Alaska 9", (54, 0), (-170, 0), 10000, 500000.0, 0.0),
+    lcc!(5010, "Alaska 10", (51, 50), (53, 50), (51, 0), (-176, 0), 1000000.0, 0.0),
+    tm!(5101, "Hawaii 1", (18, 50), (-155, 30), 30000, 500000.0, 0.0),
+    tm!(5102, "Hawaii 2", (20, 20), (-156, 40), 30000, 500000.0, 0.0),
+    tm!(5103, "Hawaii 3", (21, 10), (-158, 0), 100000, 500000.0, 0.0),
+    tm!(5104, "Hawaii 4", (21, 50), (-159, 30), 100000, 500000.0, 0.0),
+    tm!(5105, "Hawaii 5", (21, 40), (-160, 10), 0, 500000.0, 0.0),
+    lcc!(5200, "Puerto Rico and Virgin Islands", (18, 2), (18, 26), (17, 50), (-66, 26), 200000.0, 200000.0),
+];
+
+fn degrees(angle: (i16, u8)) -> f64 {
+    let (d, m) = angle;
+    let abs = d.abs() as f64 + m as f64 / 60.0;
+    if d < 0 { -abs } else { abs }
+}
+
+impl StatePlaneZone {
+    pub fn from_code(code: u16) -> Option<&'static StatePlaneZone> {
+        /*!
+        Looks up a zone by its FIPS code.
+        */
+
+        ZONES.iter().find(|z| z.code == code)
+    }
+
+    pub fn zones() -> &'static [StatePlaneZone] {
+        /*!
+        Returns all of the zones, ordered by FIPS code.
+        */
+
+        &ZONES
+    }
+
+    pub fn projection(&self) -> SpcsProjection {
+        /*!
+        Returns the projection this zone is defined by, on the GRS80 ellipsoid and in metres.
+        */
+
+        match self.parameters {
+            ZoneParameters::Tm(lat0, lon0, k0, fe, fn_) => SpcsProjection::TransverseMercator(TransverseMercator {
+                lon0: degrees(lon0),
+                lat0: degrees(lat0),
+                k0: if k0 == 0 { 1.0 } else { 1.0 - 1.0 / k0 as f64 },
+                false_easting: fe,
+                false_northing: fn_,
+                ellipsoid: Ellipsoid::Grs80,
+            }),
+            ZoneParameters::Lcc(lat1, lat2, lat0, lon0, fe, fn_) => SpcsProjection::LambertConformalConic(
+                LambertConformalConic::two_sp(degrees(lon0), degrees(lat0), degrees(lat1), degrees(lat2), fe, fn_, Ellipsoid::Grs80)
+            ),
+            ZoneParameters::Omerc(latc, lonc, azimuth, k0, fe, fn_) => SpcsProjection::HotineObliqueMercator(
+                HotineObliqueMercator::variant_a(degrees(lonc), degrees(latc), azimuth, azimuth, if k0 == 0 { 1.0 } else { 1.0 - 1.0 / k0 as f64 }, fe, fn_, Ellipsoid::Grs80)
+            ),
+        }
+    }
+}
+
+/// A NAD83 State Plane coordinate
+#[derive(Copy, Clone, Debug)]
+pub struct StatePlane {
+    /// Zone the coordinate is in
+    pub zone: &'static StatePlaneZone,
+    /// Easting in metres, including the false easting
+    pub easting: f64,
+    /// Northing in metres, including the false northing
+    pub northing: f64,
+}
+
+impl StatePlane {
+    pub fn new(code: u16, easting: f64, northing: f64, unit: LinearUnit) -> Result<Self, Errors> {
+        /*!
+        Creates a State Plane coordinate.
+
+        ### Params
+         * **code**: FIPS code of the zone.
+         * **easting**: Easting, including the false easting.
+         * **northing**: Northing, including the false northing.
+         * **unit**: Unit the easting and northing are given in.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::UnknownStatePlaneZone` if there is no zone with the FIPS code.
+
+        # Examples
+
+        ```
+        let spcs = StatePlane::new(405, 6487200.0, 1840150.0, LinearUnit::UsSurveyFoot).unwrap();
+        ```
+        */
+
+        let zone = StatePlaneZone::from_code(code).ok_or(Errors::UnknownStatePlaneZone(code))?;
+        Ok(StatePlane {
+            zone,
+            easting: easting * unit.as_metres(),
+            northing: northing * unit.as_metres(),
+        })
+    }
+
+    pub fn from_ll(ll: &LatLon, code: u16) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to a State Plane coordinate in a zone, shifting the point onto
+        the NAD83 datum first if required.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::UnknownStatePlaneZone` if there is no zone with the FIPS code.
+        */
+
+        let zone = StatePlaneZone::from_code(code).ok_or(Errors::UnknownStatePlaneZone(code))?;
+        let ll = ll.to_datum(Datum::Nad83);
+        let (easting, northing) = match zone.projection() {
+            SpcsProjection::TransverseMercator(tm) => {
+                let p = tm.forward(&ll);
+                (p.easting, p.northing)
+            },
+            SpcsProjection::LambertConformalConic(lcc) => {
+                let p = lcc.forward(&ll);
+                (p.easting, p.northing)
+            },
+            SpcsProjection::HotineObliqueMercator(omerc) => {
+                let p = omerc.forward(&ll);
+                (p.easting, p.northing)
+            },
+        };
+        Ok(StatePlane {
+            zone,
+            easting,
+            northing,
+        })
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this coordinate to WGS84 latitude/longitude.
+        */
+
+        let mut ll = match self.zone.projection() {
+            SpcsProjection::TransverseMercator(tm) => tm.inverse(self.easting, self.northing),
+            SpcsProjection::LambertConformalConic(lcc) => lcc.inverse(self.easting, self.northing),
+            SpcsProjection::HotineObliqueMercator(omerc) => omerc.inverse(self.easting, self.northing),
+        };
+        ll.datum = Datum::Nad83;
+        ll.to_datum(Datum::Wgs84)
+    }
+
+    pub fn easting_in(&self, unit: LinearUnit) -> f64 {
+        self.easting / unit.as_metres()
+    }
+
+    pub fn northing_in(&self, unit: LinearUnit) -> f64 {
+        self.northing / unit.as_metres()
+    }
+
+    pub fn as_string(&self, unit: LinearUnit, digits: usize) -> String {
+        /*!
+        Returns a string representation of a State Plane coordinate as the zone's FIPS code, the
+        easting and the northing.
+
+        ### Params
+         * **unit** Unit to give the easting and northing in
+         * **digits** Determines the number of digits to return after the decimal
+        */
+
+        format!("{0:04} {1:.3$} {2:.3$}", self.zone.code, self.easting_in(unit), self.northing_in(unit), digits)
+    }
+}
+
+impl From<StatePlane> for LatLon {
+    fn from(spcs: StatePlane) -> Self {
+        spcs.to_ll()
+    }
+}
+
+impl fmt::Display for StatePlane {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(LinearUnit::Metre, 3))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StatePlane, StatePlaneZone, LinearUnit};
+    use Errors;
+    use latlon::LatLon;
+    use datum::Datum;
+
+    // Points away from the zone origins are checked against an independent evaluation of the
+    // IOGP Guidance Note 7-2 formulas for each projection on GRS80
+
+    fn nad83(lat: f64, lon: f64) -> LatLon {
+        LatLon { lat, lon, datum: Datum::Nad83, ..Default::default() }
+    }
+
+    fn check(code: u16, lat: f64, lon: f64, unit: LinearUnit, easting: f64, northing: f64) {
+        let spcs = StatePlane::from_ll(&nad83(lat, lon), code).unwrap();
+        assert!((spcs.easting_in(unit) - easting).abs() < 0.001, "{} easting {}", code, spcs.easting_in(unit));
+        assert!((spcs.northing_in(unit) - northing).abs() < 0.001, "{} northing {}", code, spcs.northing_in(unit));
+
+        let ll = StatePlane::new(code, easting, northing, unit).unwrap().to_ll();
+        assert!((ll.lat - lat).abs() < 1e-8);
+        assert!((ll.lon - lon).abs() < 1e-8);
+    }
+
+    #[test]
+    fn zone_table() {
+        let zones = StatePlaneZone::zones();
+        assert_eq!(zones.len(), 124);
+        assert!(zones.windows(2).all(|w| w[0].code < w[1].code));
+        assert_eq!(StatePlaneZone::from_code(405).unwrap().name, "California V");
+        assert!(StatePlaneZone::from_code(9999).is_none());
+        match StatePlane::new(9999, 0.0, 0.0, LinearUnit::Metre) {
+            Err(Errors::UnknownStatePlaneZone(9999)) => (),
+            r => panic!("expected UnknownStatePlaneZone, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn origins_in_published_units() {
+        // NOAA Manual NOS NGS 5: the false origins of zones legislated in feet are round numbers
+        // of US survey feet
+        check(502, 37.0 + 50.0 / 60.0, -105.5, LinearUnit::UsSurveyFoot, 3000000.0, 1000000.0);
+        check(600, 40.0 + 50.0 / 60.0, -72.75, LinearUnit::UsSurveyFoot, 1000000.0, 500000.0);
+        check(101, 30.5, -85.0 - 50.0 / 60.0, LinearUnit::Metre, 200000.0, 0.0);
+    }
+
+    #[test]
+    fn transverse_mercator() {
+        check(101, 32.5, -86.0, LinearUnit::Metre, 184337.824, 221759.669);
+        check(4400, 44.26, -72.58, LinearUnit::UsSurveyFoot, 1619458.111, 641509.188);
+    }
+
+    #[test]
+    fn lambert_conformal_conic() {
+        check(405, 34.05, -118.25, LinearUnit::Metre, 1976917.451, 561038.044);
+        check(502, 39.74, -104.99, LinearUnit::UsSurveyFoot, 3143422.537, 1694827.344);
+    }
+
+    #[test]
+    fn across_antimeridian() {
+        // Alaska zone 10 reaches west of the antimeridian, to 172°E
+        let spcs = StatePlane::from_ll(&nad83(52.5, 179.0), 5010).unwrap();
+        let easting = spcs.easting_in(LinearUnit::Metre);
+        assert!(500000.0 < easting && easting < 1000000.0, "easting {}", easting);
+
+        let ll = spcs.to_ll();
+        assert!((ll.lat - 52.5).abs() < 1e-8);
+        assert!((ll.lon - 179.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn hotine_oblique_mercator() {
+        check(5001, 58.3019, -134.4197, LinearUnit::Metre, 774519.779, 720322.483);
+        check(5001, 58.3019, -134.4197, LinearUnit::UsSurveyFoot, 2541070.307, 2363258.012);
+    }
+
+    #[test]
+    fn format() {
+        let spcs = StatePlane::new(502, 3000000.0, 1000000.0, LinearUnit::UsSurveyFoot).unwrap();
+        assert_eq!(&*spcs.as_string(LinearUnit::UsSurveyFoot, 2), "0502 3000000.00 1000000.00");
+        assert_eq!(&*spcs.to_string(), "0502 914401.829 304800.610");
+    }
+}
@@ -0,0 +1,316 @@
+use std::str::FromStr;
+use std::fmt;
+
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use ellipsoid::Ellipsoid;
+use omerc::HotineObliqueMercator;
+use parser::{Lv03Parser, Lv95Parser};
+
+// projection centre, the old Bern observatory, at 46°57'08.66"N 7°26'22.50"E
+const LATC: f64 = 46.0 + 57.0 / 60.0 + 8.66 / 3600.0;
+const LONC: f64 = 7.0 + 26.0 / 60.0 + 22.50 / 3600.0;
+// extent of the LV95 grid over Switzerland and Liechtenstein, in metres; LV03 is the same less
+// 2000km east and 1000km north
+const MIN_EASTING: f64 = 2480e3;
+const MAX_EASTING: f64 = 2840e3;
+const MIN_NORTHING: f64 = 1070e3;
+const MAX_NORTHING: f64 = 1300e3;
+
+/// Swiss LV03 coordinate on the CH1903 datum, such as `600000 200000`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Lv03 {
+    /// Easting (y) in metres from the false origin
+    pub easting: f64,
+    /// Northing (x) in metres from the false origin
+    pub northing: f64,
+}
+
+/// Swiss LV95 coordinate on the CH1903+ datum, such as `2600000 1200000`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Lv95 {
+    /// Easting (E) in metres from the false origin
+    pub easting: f64,
+    /// Northing (N) in metres from the false origin
+    pub northing: f64,
+}
+
+impl Lv03 {
+    pub fn new(easting: f64, northing: f64) -> Result<Self, Errors> {
+        /*!
+        Creates an LV03 coordinate from an easting and northing.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::OutsideGrid` if the easting/northing falls outside of Switzerland and
+           Liechtenstein, as an LV95 coordinate would.
+        */
+
+        if !in_extent(easting + 2000e3, northing + 1000e3) { return Err(Errors::OutsideGrid); }
+        Ok(Lv03 {
+            easting,
+            northing,
+        })
+    }
+
+    pub fn projection() -> HotineObliqueMercator {
+        /*!
+        Returns the oblique Mercator projection LV03 is defined by.
+        */
+
+        projection(600e3, 200e3)
+    }
+
+    pub fn from_ll(ll: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to an LV03 coordinate, shifting the point onto the CH1903 datum
+        first if required.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::OutsideGrid` if the point falls outside of Switzerland and
+           Liechtenstein.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(46.9480, 7.4474).unwrap();
+        let lv03 = Lv03::from_ll(&ll).unwrap(); // lv03.to_string() = '600667 199657'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Ch1903);
+        let p = Lv03::projection().forward(&ll);
+        Lv03::new(p.easting, p.northing)
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this coordinate to WGS84 latitude/longitude.
+        */
+
+        let mut ll = Lv03::projection().inverse(self.easting, self.northing);
+        ll.datum = Datum::Ch1903;
+        ll.to_datum(Datum::Wgs84)
+    }
+
+    fn as_string(&self, digits: usize) -> String {
+        /*!
+        Returns a string representation of an LV03 coordinate as the easting and the northing.
+
+        ### Params
+         * **digits** Determines the number of digits to return after the decimal
+        */
+
+        format!("{0:.2$} {1:.2$}", self.easting, self.northing, digits)
+    }
+}
+
+impl Lv95 {
+    pub fn new(easting: f64, northing: f64) -> Result<Self, Errors> {
+        /*!
+        Creates an LV95 coordinate from an easting and northing.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::OutsideGrid` if the easting/northing falls outside of Switzerland and
+           Liechtenstein, as an LV03 coordinate would.
+        */
+
+        if !in_extent(easting, northing) { return Err(Errors::OutsideGrid); }
+        Ok(Lv95 {
+            easting,
+            northing,
+        })
+    }
+
+    pub fn projection() -> HotineObliqueMercator {
+        /*!
+        Returns the oblique Mercator projection LV95 is defined by.
+        */
+
+        projection(2600e3, 1200e3)
+    }
+
+    pub fn from_ll(ll: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to an LV95 coordinate, shifting the point onto the CH1903+
+        datum first if required.
+
+        ### Returns
+         * **Ok**: The coordinate.
+         * **Err**: `Errors::OutsideGrid` if the point falls outside of Switzerland and
+           Liechtenstein.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(46.9480, 7.4474).unwrap();
+        let lv95 = Lv95::from_ll(&ll).unwrap(); // lv95.to_string() = '2600667 1199657'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Ch1903Plus);
+        let p = Lv95::projection().forward(&ll);
+        Lv95::new(p.easting, p.northing)
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this coordinate to WGS84 latitude/longitude.
+        */
+
+        let mut ll = Lv95::projection().inverse(self.easting, self.northing);
+        ll.datum = Datum::Ch1903Plus;
+        ll.to_datum(Datum::Wgs84)
+    }
+
+    fn as_string(&self, digits: usize) -> String {
+        /*!
+        Returns a string representation of an LV95 coordinate as the easting and the northing.
+
+        ### Params
+         * **digits** Determines the number of digits to return after the decimal
+        */
+
+        format!("{0:.2$} {1:.2$}", self.easting, self.northing, digits)
+    }
+}
+
+fn projection(false_easting: f64, false_northing: f64) -> HotineObliqueMercator {
+    HotineObliqueMercator::variant_b(LONC, LATC, 90.0, 90.0, 1.0, false_easting, false_northing, Ellipsoid::Bessel1841)
+}
+
+fn in_extent(easting: f64, northing: f64) -> bool {
+    (MIN_EASTING..MAX_EASTING).contains(&easting) && (MIN_NORTHING..MAX_NORTHING).contains(&northing)
+}
+
+impl From<Lv03> for Lv95 {
+    /// Converts by the shift of the false origin alone, ignoring the up to 1.6m distortion of the
+    /// older LV03 frame.
+    fn from(lv03: Lv03) -> Self {
+        Lv95 {
+            easting: lv03.easting + 2000e3,
+            northing: lv03.northing + 1000e3,
+        }
+    }
+}
+
+impl From<Lv95> for Lv03 {
+    /// Converts by the shift of the false origin alone, ignoring the up to 1.6m distortion of the
+    /// older LV03 frame.
+    fn from(lv95: Lv95) -> Self {
+        Lv03 {
+            easting: lv95.easting - 2000e3,
+            northing: lv95.northing - 1000e3,
+        }
+    }
+}
+
+impl From<Lv03> for LatLon {
+    fn from(lv03: Lv03) -> Self {
+        lv03.to_ll()
+    }
+}
+
+impl From<Lv95> for LatLon {
+    fn from(lv95: Lv95) -> Self {
+        lv95.to_ll()
+    }
+}
+
+impl FromStr for Lv03 {
+    type Err = Errors;
+    /// Parses an LV03 easting and northing, separated by a comma and/or whitespace
+    /// (`600000 200000`, `600'000, 200'000`).
+    fn from_str(lv03: &str) -> Result<Self, Self::Err> {
+        Lv03Parser::new(lv03.as_bytes()).parse()
+    }
+}
+
+impl FromStr for Lv95 {
+    type Err = Errors;
+    /// Parses an LV95 easting and northing, separated by a comma and/or whitespace
+    /// (`2600000 1200000`, `2'600'000, 1'200'000`).
+    fn from_str(lv95: &str) -> Result<Self, Self::Err> {
+        Lv95Parser::new(lv95.as_bytes()).parse()
+    }
+}
+
+impl fmt::Display for Lv03 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(0))
+    }
+}
+
+impl fmt::Display for Lv95 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Lv03, Lv95, LATC, LONC};
+    use Errors;
+    use latlon::LatLon;
+    use datum::Datum;
+
+    // swisstopo 'Approximate formulas for the transformation between Swiss projection coordinates
+    // and WGS84', whose worked example is stated to be good to about a metre
+    const LAT: f64 = 46.0 + 2.0 / 60.0 + 38.87 / 3600.0;
+    const LON: f64 = 8.0 + 43.0 / 60.0 + 49.79 / 3600.0;
+
+    #[test]
+    fn bern_false_origin() {
+        let bern = LatLon { lat: LATC, lon: LONC, datum: Datum::Ch1903, ..Default::default() };
+        let lv03 = Lv03::from_ll(&bern).unwrap();
+        assert!((lv03.easting - 600000.0).abs() < 1e-6);
+        assert!((lv03.northing - 200000.0).abs() < 1e-6);
+
+        let bern = LatLon { datum: Datum::Ch1903Plus, ..bern };
+        let lv95 = Lv95::from_ll(&bern).unwrap();
+        assert!((lv95.easting - 2600000.0).abs() < 1e-6);
+        assert!((lv95.northing - 1200000.0).abs() < 1e-6);
+        assert_eq!(&*lv95.to_string(), "2600000 1200000");
+    }
+
+    #[test]
+    fn swisstopo_example() {
+        let ll = LatLon::new(LAT, LON).unwrap();
+        let lv95 = Lv95::from_ll(&ll).unwrap();
+        assert!((lv95.easting - 2699999.76).abs() < 1.0);
+        assert!((lv95.northing - 1099999.97).abs() < 1.0);
+
+        let lv03 = Lv03::from_ll(&ll).unwrap();
+        assert!((lv03.easting - 699999.76).abs() < 1.0);
+        assert!((lv03.northing - 99999.97).abs() < 1.0);
+
+        // 1e-5° is under a metre
+        let back = Lv95::new(2699999.76, 1099999.97).unwrap().to_ll();
+        assert!((back.lat - LAT).abs() < 1e-5);
+        assert!((back.lon - LON).abs() < 1e-5);
+        let back = Lv03::new(699999.76, 99999.97).unwrap().to_ll();
+        assert!((back.lat - LAT).abs() < 1e-5);
+        assert!((back.lon - LON).abs() < 1e-5);
+    }
+
+    #[test]
+    fn parse_and_convert() {
+        let lv95: Lv95 = "2'600'000, 1'200'000".parse().unwrap();
+        assert_eq!(lv95.easting, 2600000.0);
+        assert_eq!(lv95.northing, 1200000.0);
+
+        let lv03 = Lv03::from(lv95);
+        assert_eq!(&*lv03.to_string(), "600000 200000");
+        let lv03: Lv03 = "600000 200000".parse().unwrap();
+        assert_eq!(Lv95::from(lv03).easting, 2600000.0);
+
+        match "600000 200000".parse::<Lv95>() {
+            Err(Errors::OutsideGrid) => (),
+            r => panic!("expected OutsideGrid, got {:?}", r),
+        }
+        assert!(Lv03::from_ll(&LatLon::new(48.8582, 2.2945).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,307 @@
+use Lat;
+use Lon;
+use latlon::LatLon;
+use ellipsoid::Ellipsoid;
+
+/// Transverse Mercator projection with arbitrary parameters
+///
+/// Implements Karney’s method, using Krüger series to order n^6, giving results accurate to 5nm
+/// for distances up to 3900km from the central meridian. UTM, Gauss-Krüger and most national grids
+/// are this projection with particular parameters.
+#[derive(Copy, Clone, Debug)]
+pub struct TransverseMercator {
+    /// Longitude of the central meridian, in degrees
+    pub lon0: Lon,
+    /// Latitude of the origin, in degrees
+    pub lat0: Lat,
+    /// Scale factor on the central meridian
+    pub k0: f64,
+    /// Easting of the origin, in metres
+    pub false_easting: f64,
+    /// Northing of the origin, in metres
+    pub false_northing: f64,
+    /// Ellipsoid the projection is defined on
+    pub ellipsoid: Ellipsoid,
+}
+
+/// A point projected onto a Transverse Mercator grid
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TmCoord {
+    /// Easting in metres, including the false easting
+    pub easting: f64,
+    /// Northing in metres, including the false northing
+    pub northing: f64,
+    /// Meridian convergence (bearing of grid north clockwise from true north), in degrees
+    pub convergence: f64,
+    /// Grid scale factor
+    pub scale: f64,
+}
+
+impl TransverseMercator {
+    pub fn forward(&self, ll: &LatLon) -> TmCoord {
+        /*!
+        Projects latitude/longitude onto this grid.
+
+        ### Params
+         * **ll**: The point to project, on this projection's ellipsoid.
+
+        ### Returns
+         * The `TmCoord` easting, northing, convergence and scale of the point.
+        */
+
+        let (x, y, gamma, k) = self.project(ll.lat, ll.lon);
+        // northing of the origin, so the grid can be anchored to a latitude other than the equator
+        let (_, y0, _, _) = self.project(self.lat0, self.lon0);
+
+        TmCoord {
+            easting: x + self.false_easting,
+            northing: y - y0 + self.false_northing,
+            convergence: gamma.to_degrees(),
+            scale: k,
+        }
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    pub fn inverse(&self, easting: f64, northing: f64) -> LatLon {
+        /*!
+        Converts a grid easting/northing back to latitude/longitude.
+
+        ### Params
+         * **easting**: Easting in metres, including the false easting.
+         * **northing**: Northing in metres, including the false northing.
+
+        ### Returns
+         * The `LatLon` of the point, with the convergence and scale at that point.
+        */
+
+        let (_, y0, _, _) = self.project(self.lat0, self.lon0);
+        let x = easting - self.false_easting;      // make x ± relative to central meridian
+        let y = northing - self.false_northing + y0; // make y ± relative to equator
+
+        let a = self.ellipsoid.a();
+        let f = self.ellipsoid.f();
+        let k0 = self.k0;
+
+        // ---- from Karney 2011 Eq 15-22, 36:
+
+        let e = f64::sqrt(f * (2.0 - f)); // eccentricity
+        let n = f / (2.0 - f);        // 3rd flattening
+        let n2 = n * n;
+        let n3 = n * n2;
+        let n4 = n * n3;
+        let n5 = n * n4;
+        let n6 = n * n5;
+
+        #[allow(non_snake_case)]
+        let A = a / (1.0 + n) * (1.0 + 1.0 / 4.0 * n2 + 1.0 / 64.0 * n4 + 1.0 / 256.0 * n6); // 2πA is the circumference of a meridian
+
+        let eta = x / (k0*A);
+        let xi = y / (k0*A);
+
+        let beta = [ 0.0, // note beta is one-based array (6th order Krüger expressions)
+            1.0 / 2.0 * n - 2.0 / 3.0 * n2 + 37.0 / 96.0 * n3 - 1.0 / 360.0 * n4 - 81.0 / 512.0 * n5 + 96199.0 / 604800.0 * n6,
+            1.0 / 48.0 * n2 + 1.0 / 15.0 * n3 - 437.0 / 1440.0 * n4 + 46.0 / 105.0 * n5 - 1118711.0 / 3870720.0 * n6,
+            17.0 / 480.0 * n3 - 37.0 / 840.0 * n4 - 209.0 / 4480.0 * n5 + 5569.0 / 90720.0 * n6,
+            4397.0 / 161280.0 * n4 - 11.0 / 504.0 * n5 - 830251.0 / 7257600.0 * n6,
+            4583.0 / 161280.0 * n5 - 108847.0 / 3991680.0 * n6,
+            20648693.0 / 638668800.0 * n6 ];
+
+        let mut xi2 = xi;
+        for j in 1..7 { xi2 -= beta[j] * f64::sin(2.0 * j as f64 * xi) * f64::cosh(2.0 * j as f64 * eta); }
+
+        let mut eta2 = eta;
+        for j in 1..7 { eta2 -= beta[j] * f64::cos(2.0 * j as f64* xi) * f64::sinh(2.0 * j as f64 * eta); }
+
+        let sinheta2 = f64::sinh(eta2);
+        let sinxi2 = f64::sin(xi2);
+        let cosxi2 = f64::cos(xi2);
+
+        let tau2 = sinxi2 / f64::sqrt(sinheta2 * sinheta2 + cosxi2 * cosxi2);
+
+        let mut taui = tau2;
+        loop {
+            let sigmai = f64::sinh(e * f64::atanh(e * taui / f64::sqrt(1.0 + taui * taui)));
+            let taui2 = taui * f64::sqrt(1.0 + sigmai * sigmai) - sigmai * f64::sqrt(1.0 + taui * taui);
+            let deltataui = (tau2 - taui2) / f64::sqrt(1.0 + taui2 * taui2)
+                * (1.0 + (1.0 - e * e) * taui * taui) / ((1.0 - e * e) * f64::sqrt(1.0 + taui * taui));
+             taui += deltataui;
+            if f64::abs(deltataui) <= 1e-12 || deltataui.is_nan() { break; } // using IEEE 754 deltataui -> 0 after 2-3 iterations
+        }
+        // note relatively large convergence test as deltataui toggles on ±1.12e-16 for eg 31 N 400000 5000000
+        let tau = taui;
+
+        let phi = f64::atan(tau);
+
+        let lamda = f64::atan2(sinheta2, cosxi2);
+
+        // ---- convergence: Karney 2011 Eq 26, 27
+
+        let mut p = 1.0;
+        for j in 1..7 { p -= 2.0 * j as f64 * beta[j] * f64::cos(2.0 * j as f64 * xi) * f64::cosh(2.0 * j as f64 * eta); }
+        let mut q = 0.0;
+        for j in 1..7 { q += 2.0 * j as f64 * beta[j] * f64::sin(2.0 * j as f64 * xi) * f64::sinh(2.0 * j as f64 * eta); }
+
+        let gamma2 = f64::atan(f64::tan(xi2) * f64::tanh(eta2));
+        let gamma3 = f64::atan2(q, p);
+
+        let gamma = gamma2 + gamma3;
+
+        // ---- scale: Karney 2011 Eq 28
+
+        let sinphi = f64::sin(phi);
+        let k2 = f64::sqrt(1.0 - e * e * sinphi * sinphi) * f64::sqrt(1.0 + tau * tau) * f64::sqrt(sinheta2 *sinheta2 + cosxi2 * cosxi2);
+        let k3 = A / a / f64::sqrt(p * p + q * q);
+
+        let k = k0 * k2 * k3;
+
+        // move lamda from zonal to global coordinates, back into -180..180 for grids extended across
+        // the antimeridian
+        let mut lon = lamda.to_degrees() + self.lon0;
+        if lon > 180.0 { lon -= 360.0; } else if lon < -180.0 { lon += 360.0; }
+
+        LatLon {
+            lat: phi.to_degrees(),
+            lon,
+            convergence: Some(gamma.to_degrees()),
+            scale: Some(k),
+            ..Default::default()
+        }
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn project(&self, lat: Lat, lon: Lon) -> (f64, f64, f64, f64) {
+        /*!
+        Projects latitude/longitude relative to the central meridian and equator, before any
+        false origin is applied.
+
+        ### Returns
+         * `(x, y, gamma, k)`, with the convergence `gamma` in radians.
+        */
+
+        let phi = f64::to_radians(lat);      // latitude ± from equator
+        let lamda = f64::to_radians(lon - self.lon0); // longitude ± from central meridian
+
+        let a = self.ellipsoid.a();
+        let f = self.ellipsoid.f();
+        let k0 = self.k0;
+
+        // ---- easting, northing: Karney 2011 Eq 7-14, 29, 35:
+
+        let e = f64::sqrt(f * (2.0 - f)); // eccentricity
+        let n = f / (2.0 - f);        // 3rd flattening
+        let n2 = n * n;
+        let n3 = n * n2;
+        let n4 = n * n3;
+        let n5 = n * n4;
+        let n6 = n * n5; // TODO: compare Horner-form accuracy?
+
+        let coslamda = lamda.cos();
+        let sinlamda = lamda.sin();
+        let tanlamda = lamda.tan();
+
+        // tau ≡ tanphi, tau2 ≡ tanphi2; prime (2) indicates angles on the conformal sphere
+        let tau = phi.tan();
+        let delta = f64::sinh(e * f64::atanh(e * tau / f64::sqrt( 1.0 + tau * tau)));
+
+        let tau2 = tau * f64::sqrt( 1.0 + delta * delta) - delta * f64::sqrt( 1.0 + tau * tau);
+
+        let mut xi = f64::atan2(tau2, coslamda);
+        let mut eta = f64::asinh(sinlamda / f64::sqrt(tau2 * tau2 + coslamda * coslamda));
+
+        // 2πA is the circumference of a meridian
+        #[allow(non_snake_case)]
+        let A = a / (1.0 + n) * (1.0 + 1.0 / 4.0 * n2 + 1.0 / 64.0 * n4 + 1.0 / 256.0 * n6);
+
+        // note alpha is one-based array (6th order Krüger expressions)
+        let alpha = [ 0.0,
+            1.0 / 2.0 *n - 2.0 / 3.0 * n2 + 5.0 / 16.0 * n3 + 41.0 / 180.0 * n4 - 127.0 / 288.0 * n5 + 7891.0 / 37800.0 * n6,
+            13.0 / 48.0 * n2 - 3.0 / 5.0 * n3 + 557.0 / 1440.0 * n4 + 281.0 / 630.0 * n5 - 1983433.0 / 1935360.0 * n6,
+            61.0 / 240.0 * n3 - 103.0 / 140.0 * n4 + 15061.0 / 26880.0 * n5 + 167603.0 / 181440.0 * n6,
+            49561.0 / 161280.0 * n4 - 179.0 / 168.0 * n5 + 6601661.0 / 7257600.0 * n6,
+            34729.0 / 80640.0 * n5 - 3418889.0 / 1995840.0 * n6,
+            212378941.0 / 319334400.0 * n6 ];
+
+        let xi2 = xi;
+        for j in 1..7 { xi += alpha[j] * f64::sin(2.0 * j as f64 * xi2) * f64::cosh(2.0 * j as f64 * eta); }
+
+        let eta2 = eta;
+        for j in 1..7 { eta += alpha[j] * f64::cos(2.0 * j as f64 * xi2) * f64::sinh(2.0 * j as f64 *eta2); }
+
+        let x = k0 * A * eta;
+        let y = k0 * A * xi;
+
+        // ---- convergence: Karney 2011 Eq 23, 24
+
+        let mut p2 = 1.0;
+        for j in 1..7 { p2 += 2.0 * j as f64 * alpha[j] * f64::cos( 2.0 * j as f64 * xi2) * f64::cosh(2.0 * j as f64 * eta2); }
+        let mut q2 = 0.0;
+        for j in 1..7 { q2 += 2.0 * j as f64 * alpha[j] * f64::sin(2.0 * j as f64 * xi2) * f64::sinh(2.0 * j as f64 * eta2); }
+
+        let gamma2 = f64::atan(tau2 / f64::sqrt(1.0 + tau2 * tau2) * tanlamda);
+        let gamma3 = q2.atan2(p2);
+
+        let gamma = gamma2 + gamma3;
+
+        // ---- scale: Karney 2011 Eq 25
+
+        let sinphi = phi.sin();
+        let k2 = f64::sqrt(1.0 - e * e * sinphi * sinphi) * f64::sqrt(1.0 + tau * tau) / f64::sqrt(tau2 * tau2 + coslamda * coslamda);
+        let k3 = A / a * f64::sqrt(p2 * p2 + q2 * q2);
+
+        let k = k0 * k2 * k3;
+
+        (x, y, gamma, k)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TransverseMercator;
+    use ellipsoid::Ellipsoid;
+    use latlon::LatLon;
+
+    #[test]
+    fn utm_parameters() {
+        let tm = TransverseMercator {
+            lon0: 3.0, lat0: 0.0, k0: 0.9996, false_easting: 500000.0, false_northing: 0.0,
+            ellipsoid: Ellipsoid::Wgs84
+        };
+        let p = tm.forward(&LatLon::new(48.8582, 2.2945).unwrap());
+        assert_eq!(p.easting.round(), 448252.0);
+        assert_eq!(p.northing.round(), 5411933.0);
+    }
+
+    #[test]
+    fn inverse_across_antimeridian() {
+        // zone 60, extended 5° east of its edge
+        let tm = TransverseMercator {
+            lon0: 177.0, lat0: 0.0, k0: 0.9996, false_easting: 500000.0, false_northing: 0.0,
+            ellipsoid: Ellipsoid::Wgs84
+        };
+        let p = tm.forward(&LatLon::new(-16.5, -178.0).unwrap());
+        assert!(p.easting > 1000000.0);
+        let ll = tm.inverse(p.easting, p.northing);
+        assert!((ll.lat - -16.5).abs() < 1e-9);
+        assert!((ll.lon - -178.0).abs() < 1e-9);
+
+        let west = TransverseMercator { lon0: -177.0, ..tm };
+        let p = west.forward(&LatLon::new(-16.5, 178.0).unwrap());
+        assert!((west.inverse(p.easting, p.northing).lon - 178.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn round_trip_with_origin_latitude() {
+        let tm = TransverseMercator {
+            lon0: -2.0, lat0: 49.0, k0: 0.9996012717, false_easting: 400000.0,
+            false_northing: -100000.0, ellipsoid: Ellipsoid::Grs80
+        };
+        let origin = tm.forward(&LatLon::new(49.0, -2.0).unwrap());
+        assert!((origin.easting - 400000.0).abs() < 1e-6);
+        assert!((origin.northing - -100000.0).abs() < 1e-6);
+
+        let p = tm.forward(&LatLon::new(52.0, 1.0).unwrap());
+        let ll = tm.inverse(p.easting, p.northing);
+        assert!((ll.lat - 52.0).abs() < 1e-9);
+        assert!((ll.lon - 1.0).abs() < 1e-9);
+    }
+}
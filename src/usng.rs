@@ -0,0 +1,261 @@
+use std::str::FromStr;
+use std::fmt;
+
+use Utm;
+use Mgrs;
+use Accuracy;
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+use gzd::{Gzd, GridSquareId100k};
+use col::ColLetter;
+use parser::UsngParser;
+
+/// How much of the leading part of a USNG reference is left off when the area is already known
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Truncation {
+    /// The full reference, such as `18S UJ 23487 06483`
+    Full,
+    /// The grid zone designator is left off, such as `UJ 23487 06483`
+    GridSquare,
+    /// The grid zone designator and 100km square are left off, such as `23487 06483`
+    Digits,
+}
+
+/// A possibly truncated USNG reference, as read before resolving it against a context point
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UsngRef {
+    pub gzd: Option<Gzd>,
+    pub gsid_100k: Option<GridSquareId100k>,
+    pub easting: usize,
+    pub northing: usize,
+    pub accuracy: Accuracy,
+}
+
+/// US National Grid reference
+///
+/// USNG references are MGRS references on the NAD83 datum, written with spaces between the grid
+/// zone designator, the 100km square and the easting/northing, and without padding the zone
+/// number (`4Q FJ 12345 67890`).
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Usng {
+    pub mgrs: Mgrs,
+}
+
+impl Usng {
+    pub fn from_ll(ll: &LatLon, accuracy: Accuracy) -> Self {
+        /*!
+        Converts latitude/longitude to a USNG reference, shifting the point onto the NAD83 datum
+        first if required.
+
+        ### Params
+         * **ll**: The point to convert.
+         * **accuracy**: Precision the resulting grid reference is reported at.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(38.8895, -77.0352).unwrap();
+        let usng = Usng::from_ll(&ll, Accuracy::One); // usng.to_string() = '18S UJ 23487 06483'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Nad83);
+        Usng { mgrs: Utm::from_ll(&ll).to_mgrs(accuracy) }
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this grid reference to WGS84 latitude/longitude.
+        */
+
+        let mut utm = Utm::from(self.mgrs);
+        utm.datum = Datum::Nad83;
+        LatLon::from(utm).to_datum(Datum::Wgs84)
+    }
+
+    pub fn from_str_near(usng: &str, context: &LatLon) -> Result<Self, Errors> {
+        /*!
+        Parses a USNG reference which may be truncated, resolving any left off grid zone designator
+        and 100km square to those placing the point nearest `context`.
+
+        ### Params
+         * **usng**: The reference, in any of the `Truncation` forms.
+         * **context**: A point within the area the reference was given for, such as the centre of
+           an incident or the map in use.
+
+        ### Returns
+         * **Ok**: The full grid reference.
+         * **Err**: `Errors::InvalidGridReference` if `usng` cannot be parsed, or the error from
+           expressing `context` in the zone the 100km square belongs to.
+
+        # Examples
+
+        ```
+        let context = LatLon::new(38.8895, -77.0352).unwrap();
+        let usng = Usng::from_str_near("UJ 2349 0648", &context).unwrap();
+        // usng.to_string() = '18S UJ 2349 0648'
+        ```
+        */
+
+        let r = UsngParser::new(usng.as_bytes()).parse()?;
+        if let (Some(gzd), Some(gsid_100k)) = (r.gzd, r.gsid_100k) {
+            return Ok(Usng { mgrs: Mgrs { gzd, gsid_100k, easting: r.easting, northing: r.northing, accuracy: r.accuracy } });
+        }
+
+        let context = Utm::from_ll(&context.to_datum(Datum::Nad83));
+        let utm = match r.gsid_100k {
+            Some(gsid_100k) => {
+                // of the context zone and its neighbours, only one has this column letter
+                let zone = [context.zone, context.zone % 60 + 1, (context.zone + 58) % 60 + 1].iter()
+                    .cloned()
+                    .find(|z| column_set(gsid_100k.col) == (z - 1) % 3)
+                    .expect("column letter matches no zone");
+                let context = context.to_zone(zone)?;
+                let easting = gsid_100k.col.as_meters_from_zone(zone) + r.easting;
+                let northing = gsid_100k.row.as_meters_from_zone(zone) + r.northing;
+                Utm {
+                    easting: easting as i32,
+                    northing: nearest(northing as i32, context.northing, 2000000),
+                    ..context
+                }
+            },
+            None => Utm {
+                easting: nearest(r.easting as i32, context.easting, 100000),
+                northing: nearest(r.northing as i32, context.northing, 100000),
+                ..context
+            },
+        };
+        Ok(Usng { mgrs: utm.to_mgrs(r.accuracy) })
+    }
+
+    pub fn truncated(&self, truncation: Truncation) -> String {
+        /*!
+        Returns this grid reference with its leading part left off, for use within an area where
+        it is already known.
+
+        # Examples
+
+        ```
+        let usng: Usng = "18S UJ 23487 06483".parse().unwrap();
+        assert_eq!("23487 06483", &*usng.truncated(Truncation::Digits));
+        ```
+        */
+
+        let s = self.as_string(self.mgrs.accuracy);
+        let skip = match truncation {
+            Truncation::Full => 0,
+            Truncation::GridSquare => 1,
+            Truncation::Digits => 2,
+        };
+        s.splitn(skip + 1, ' ').last().unwrap_or("").to_owned()
+    }
+
+    fn as_string(&self, accuracy: Accuracy) -> String {
+        /*!
+        Returns a string representation of a USNG reference, always separated by spaces.
+
+        ### Params
+         * **accuracy** Precision of returned grid reference (eg `One` = 1m or 10 digit grid,
+           `Ten` = 10m or 8 digit grid, etc.).
+        */
+
+        let mgrs = &self.mgrs;
+        let digits: i32 = accuracy.as_num_digits() as i32 / 2;
+        // set required precision
+        let easting = (f64::floor(mgrs.easting as f64 / f64::powi(10.0, 5 - digits))) as usize;
+        let northing = (f64::floor(mgrs.northing as f64 / f64::powi(10.0, 5 - digits))) as usize;
+        if digits == 0 {
+            return format!("{0}{1} {2}{3}", mgrs.gzd.zone, mgrs.gzd.band, mgrs.gsid_100k.col, mgrs.gsid_100k.row);
+        }
+
+        format!("{0}{1} {2}{3} {4:0>6$} {5:0>6$}", mgrs.gzd.zone, mgrs.gzd.band, mgrs.gsid_100k.col, mgrs.gsid_100k.row, easting, northing, digits as usize)
+    }
+}
+
+/// Returns the 100km column letter set (0..3) a column letter belongs to
+fn column_set(col: ColLetter) -> u8 {
+    col as u8 / 8
+}
+
+/// Returns the value congruent to `value` modulo `period` which lies nearest to `target`
+fn nearest(value: i32, target: i32, period: i32) -> i32 {
+    value + f64::round((target - value) as f64 / period as f64) as i32 * period
+}
+
+impl From<Mgrs> for Usng {
+    fn from(mgrs: Mgrs) -> Self {
+        Usng { mgrs }
+    }
+}
+
+impl From<Usng> for Mgrs {
+    fn from(usng: Usng) -> Self {
+        usng.mgrs
+    }
+}
+
+impl From<Usng> for LatLon {
+    fn from(usng: Usng) -> Self {
+        usng.to_ll()
+    }
+}
+
+impl FromStr for Usng {
+    type Err = Errors;
+    /// Parses a full USNG reference, spaced or not (`18S UJ 23487 06483`, `18SUJ2348706483`).
+    /// Truncated references need `Usng::from_str_near`.
+    fn from_str(usng: &str) -> Result<Self, Self::Err> {
+        let r = UsngParser::new(usng.as_bytes()).parse()?;
+        match (r.gzd, r.gsid_100k) {
+            (Some(gzd), Some(gsid_100k)) => Ok(Usng {
+                mgrs: Mgrs { gzd, gsid_100k, easting: r.easting, northing: r.northing, accuracy: r.accuracy }
+            }),
+            _ => Err(Errors::TruncatedReference),
+        }
+    }
+}
+
+impl fmt::Display for Usng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_string(self.mgrs.accuracy))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Usng, Truncation};
+    use latlon::LatLon;
+    use Accuracy;
+
+    #[test]
+    fn format_and_truncate() {
+        let ll = LatLon::new(38.8895, -77.0352).unwrap();
+        let usng = Usng::from_ll(&ll, Accuracy::Ten);
+        assert_eq!(&*usng.to_string(), "18S UJ 2348 0648");
+        assert_eq!(&*usng.truncated(Truncation::GridSquare), "UJ 2348 0648");
+        assert_eq!(&*usng.truncated(Truncation::Digits), "2348 0648");
+
+        let usng: Usng = "4QFJ1234567890".parse().unwrap();
+        assert_eq!(&*usng.to_string(), "4Q FJ 12345 67890");
+        assert!("FJ 12345 67890".parse::<Usng>().is_err());
+    }
+
+    #[test]
+    fn resolve_truncated() {
+        let context = LatLon::new(38.8895, -77.0352).unwrap();
+        let usng = Usng::from_str_near("UJ 2349 0648", &context).unwrap();
+        assert_eq!(&*usng.to_string(), "18S UJ 2349 0648");
+
+        // digits only resolve to the neighbouring 100km square nearest the context point
+        let usng = Usng::from_str_near("99000 06483", &context).unwrap();
+        assert_eq!(&*usng.to_string(), "18S TJ 99000 06483");
+
+        // the 100km square may be in the neighbouring zone
+        let context = LatLon::new(39.0, -78.05).unwrap();
+        let usng = Usng::from_str_near("SJ 00000 00000", &context).unwrap();
+        assert_eq!(usng.mgrs.gzd.zone, 18);
+        let usng = Usng::from_str_near("RJ 00000 00000", &context).unwrap();
+        assert_eq!(usng.mgrs.gzd.zone, 17);
+    }
+}
@@ -1,7 +1,6 @@
 use std::fmt;
 
 use gzd::{Gzd, GridSquareId100k};
-use get_100k_set_for_zone;
 use latlon::LatLon;
 use Mgrs;
 use Accuracy;
@@ -10,6 +9,16 @@ use datum::Datum;
 use hemisphere::Hemisphere;
 use col::ColLetter;
 use row::RowLetter;
+use Errors;
+use zone_policy::ZonePolicy;
+use tm::TransverseMercator;
+
+// UTM false easting, in metres
+const FALSE_EASTING: f64 = 500e3;
+// UTM false northing for the southern hemisphere, in metres
+const FALSE_NORTHING: f64 = 10000e3;
+// distance from the central meridian up to which the Krüger series hold to 5nm, in metres
+const MAX_TM_EXTENT: f64 = 3900e3;
 
 #[derive(Default, Copy, Clone, Debug)]
 pub struct Utm {
@@ -31,9 +40,8 @@ pub struct Utm {
 }
 
 impl Utm {
-    fn new<H, D>(zone: u8, hemisphere: H, easting: i32, northing: i32) -> Self
-        where H: Into<Hemisphere>,
-              D: Into<Datum> {
+    fn new<H>(zone: u8, hemisphere: H, easting: i32, northing: i32) -> Self
+        where H: Into<Hemisphere> {
         /*!
         Creates a `Utm` coordinate struct.
 
@@ -55,16 +63,16 @@ impl Utm {
         values are 1..60 inclusive
         */
 
-        if !(1<=zone && zone<=60) { panic!("Invalid UTM zone {}", zone); }
+        if !(1..=60).contains(&zone) { panic!("Invalid UTM zone {}", zone); }
         // range-check easting/northing (with 40km overlap between zones) - this this worthwhile?
         //if (!(120e3<=easting && easting<=880e3)) throw new Error('Invalid UTM easting '+ easting);
         //if (!(0<=northing && northing<=10000e3)) throw new Error('Invalid UTM northing '+ northing);
 
         Utm {
-            zone: zone,
+            zone,
             hemisphere: hemisphere.into(),
-            easting: easting,
-            northing: northing,
+            easting,
+            northing,
             datum: Datum::Wgs84,
             convergence: None,
             scale: None,
@@ -86,128 +94,164 @@ impl Utm {
           var utmCoord = latlong.toUtm(); // utmCoord.toString(): '31 N 448252 5411933'
         */
 
-        let false_easting = 500e3;
-        let false_northing = 10000e3;
+        let zone = ZonePolicy::Standard.zone_for(ll).expect("standard UTM zones are always valid");
+        Utm::from_ll_in_zone(ll, zone)
+    }
 
-        let mut zone = (f64::floor((ll.lon + 180.0) / 6.0) + 1.0) as u8; // longitudinal zone
-        let mut lamda0 = f64::to_radians(((zone - 1) * 6 - 180 + 3) as f64); // longitude of central meridian
+    pub fn from_ll_with_policy(ll: &LatLon, policy: ZonePolicy) -> Result<Self, Errors> {
+        /*!
+        Converts latitude/longitude to UTM coordinate, choosing the zone according to `policy`.
 
-        // ---- handle Norway/Svalbard exceptions
-        // grid zones are 8° tall; 0°N is offset 10 into latitude bands array
-        let mgrs_lat_bands = b"CDEFGHJKLMNPQRSTUVWXX"; // X is repeated for 80-84°N
-        let lat_band = mgrs_lat_bands[f64::floor(ll.lat / 8.0 + 10.0) as usize];
+        Forcing a single zone (or the zone nearest a dataset's centroid) projects a whole dataset
+        into one consistent grid. See `Utm::to_zone` for the projection limits.
 
-        // adjust zone & central meridian for Norway
-        if zone == 31 && lat_band == b'V' && ll.lon >= 3.0 { zone += 1; lamda0 += f64::to_radians(6.0); }
-        // adjust zone & central meridian for Svalbard
-        if (zone == 32 && lat_band == b'X' && ll.lon <  9.0)  { zone -= 1; lamda0 -= f64::to_radians(6.0); }
-        if (zone == 32 && lat_band == b'X' && ll.lon >= 9.0)  { zone += 1; lamda0 += f64::to_radians(6.0); }
-        if (zone == 34 && lat_band == b'X' && ll.lon <  21.0) { zone -= 1; lamda0 -= f64::to_radians(6.0); }
-        if (zone == 34 && lat_band == b'X' && ll.lon >= 21.0) { zone += 1; lamda0 += f64::to_radians(6.0); }
-        if (zone == 36 && lat_band == b'X' && ll.lon <  33.0) { zone -= 1; lamda0 -= f64::to_radians(6.0); }
-        if (zone == 36 && lat_band == b'X' && ll.lon >= 33.0) { zone += 1; lamda0 += f64::to_radians(6.0); }
+        ### Params
+         * **ll**: The point to convert.
+         * **policy**: How the UTM zone is chosen.
 
-        let phi = f64::to_radians(ll.lat);      // latitude ± from equator
-        let lamda = f64::to_radians(ll.lon) - lamda0; // longitude ± from central meridian
+        ### Returns
+         * **Ok**: The UTM coordinate in the chosen zone.
+         * **Err**: `Errors::InvalidLatitude` if the point lies outside 80°S..84°N,
+           `Errors::InvalidZone` if a forced zone is not 1..60, `Errors::InvalidLongitude` if the
+           longitude of `NearestCentralMeridian` is not finite, or `Errors::ZoneOutOfRange` if the
+           point lies further than 3900km from the central meridian of the chosen zone.
 
-        // WGS 84: a = 6378137, b = 6356752.314245, f = 1/298.257223563;
-        let a = Datum::Wgs84.a();
-        let f = Datum::Wgs84.f();
+        # Examples
 
-        let k0 = 0.9996; // UTM scale on the central meridian
+        ```
+        let ll = LatLon::new(60.0, 4.0).unwrap();
+        assert_eq!(Utm::from_ll_with_policy(&ll, ZonePolicy::Standard).unwrap().zone, 32);
+        assert_eq!(Utm::from_ll_with_policy(&ll, ZonePolicy::IgnoreExceptions).unwrap().zone, 31);
+        assert_eq!(Utm::from_ll_with_policy(&ll, ZonePolicy::Force(33)).unwrap().zone, 33);
+        ```
+        */
 
-        // ---- easting, northing: Karney 2011 Eq 7-14, 29, 35:
+        if !(-80.0 <= ll.lat && ll.lat <= 84.0) { return Err(Errors::InvalidLatitude(ll.lat)); }
+        let zone = policy.zone_for(ll)?;
 
-        let e = f64::sqrt(f * (2.0 - f)); // eccentricity
-        let n = f / (2.0 - f);        // 3rd flattening
-        let n2 = n * n;
-        let n3 = n * n2;
-        let n4 = n * n3;
-        let n5 = n * n4;
-        let n6 = n * n5; // TODO: compare Horner-form accuracy?
+        // the projection folds back on itself beyond 90° from the central meridian
+        let lon0 = (zone as i32 - 1) as f64 * 6.0 - 180.0 + 3.0;
+        let dlon = (ll.lon - lon0 + 540.0) % 360.0 - 180.0;
+        if f64::abs(dlon) >= 90.0 { return Err(Errors::ZoneOutOfRange(zone)); }
 
-        let coslamda = lamda.cos();
-        let sinlamda = lamda.sin();
-        let tanlamda = lamda.tan();
+        let utm = Utm::from_ll_in_zone(ll, zone);
+        if f64::abs(utm.easting as f64 - FALSE_EASTING) > MAX_TM_EXTENT {
+            return Err(Errors::ZoneOutOfRange(zone));
+        }
+        Ok(utm)
+    }
 
-        // tau ≡ tanphi, tau2 ≡ tanphi2; prime (2) indicates angles on the conformal sphere
-        let tau = phi.tan();
-        let delta = f64::sinh(e * f64::atanh(e * tau / f64::sqrt( 1.0 + tau * tau)));
+    pub fn to_zone(&self, zone: u8) -> Result<Self, Errors> {
+        /*!
+        Reprojects this coordinate into a forced UTM zone.
 
-        let tau2 = tau * f64::sqrt( 1.0 + delta * delta) - delta * f64::sqrt( 1.0 + tau * tau);
+        Points near a zone boundary are often expressed in the neighbouring zone (e.g.
+        `24XWT783908` and `25XEN041865` refer to the same point). The Krüger series used by
+        `Utm::from_ll` remain accurate to 5nm out to roughly 3900km from the central meridian, so
+        the target zone does not need to be adjacent, and the resulting easting may fall outside of
+        the usual 100km..900km range.
 
-        let xi = f64::atan2(tau2, coslamda);
-        let eta = f64::asinh(sinlamda / f64::sqrt(tau2 * tau2 + coslamda * coslamda));
+        ### Params
+         * **zone**: The UTM zone (1..60 inclusive) to express this coordinate in.
 
-        // 2πA is the circumference of a meridian
-        let A = a / (1.0 + n) * (1.0 + 1.0 / 4.0 * n2 + 1.0 / 64.0 * n4 + 1.0 / 256.0 * n6);
+        ### Returns
+         * **Ok**: This coordinate in the requested zone.
+         * **Err**: `Errors::InvalidZone` if `zone` is not 1..60, or `Errors::ZoneOutOfRange` if
+           this coordinate lies further than 3900km from the central meridian of `zone`.
 
-        // note alpha is one-based array (6th order Krüger expressions)
-        let alpha = [ 0.0,
-            1.0 / 2.0 *n - 2.0 / 3.0 * n2 + 5.0 / 16.0 * n3 + 41.0 / 180.0 * n4 - 127.0 / 288.0 * n5 + 7891.0 / 37800.0 * n6,
-            13.0 / 48.0 * n2 - 3.0 / 5.0 * n3 + 557.0 / 1440.0 * n4 + 281.0 / 630.0 * n5 - 1983433.0 / 1935360.0 * n6,
-            61.0 / 240.0 * n3 - 103.0 / 140.0 * n4 + 15061.0 / 26880.0 * n5 + 167603.0 / 181440.0 * n6,
-            49561.0 / 161280.0 * n4 - 179.0 / 168.0 * n5 + 6601661.0 / 7257600.0 * n6,
-            34729.0 / 80640.0 * n5 - 3418889.0 / 1995840.0 * n6,
-            212378941.0 / 319334400.0 * n6 ];
+        # Examples
 
-        let xi2 = xi;
-        for j in 1..6 { xi += alpha[j] * f64::sin(2.0 * j as f64 * xi2) * f64::cosh(2.0 * j as f64 * eta); }
+        ```
+        let utm: Utm = LatLon::new(83.62778, -32.66433).unwrap().into();
+        assert_eq!(utm.zone, 25);
+        let utm = utm.to_zone(24).unwrap();
+        assert_eq!(utm.zone, 24);
+        ```
+        */
 
-        let eta2 = eta;
-        for j in 1..6 { eta += alpha[j] * f64::cos(2.0 * j as f64 * xi2) * f64::sinh(2.0 * j as f64 *eta2); }
+        if zone == self.zone { return Ok(*self); }
 
-        let x = k0 * A * eta;
-        let y = k0 * A * xi;
+        Utm::from_ll_with_policy(&LatLon::from(*self), ZonePolicy::Force(zone))
+    }
 
-        // ---- convergence: Karney 2011 Eq 23, 24
+    pub fn offset(&self, de: f64, dn: f64) -> Result<Self, Errors> {
+        /*!
+        Moves this coordinate by a distance along the grid, such as 350m east and 1.2km north.
 
-        let p2 = 1.0;
-        for j in 1..6 { p2 += 2.0 * j as f64 * alpha[j] * f64::cos( 2.0 * j as f64 * xi2) * f64::cosh(2.0 * j as f64 * eta2); }
-        let q2 = 0.0;
-        for j in 1..6 { q2 += 2.0 * j as f64 * alpha[j] * f64::sin(2.0 * j as f64 * xi2) * f64::sinh(2.0 * j as f64 * eta2); }
+        While the moved point stays within this zone and hemisphere only the easting/northing
+        change. Otherwise the point is re-expressed in the zone and hemisphere it falls within,
+        taking the Norway and Svalbard exceptions into account.
 
-        let gamma2 = f64::atan(tau2 / f64::sqrt(1.0 + tau2 * tau2) * tanlamda);
-        let gamma3 = q2.atan2(p2);
+        ### Params
+         * **de**: Distance to move east (negative for west), in metres along grid east.
+         * **dn**: Distance to move north (negative for south), in metres along grid north.
 
-        let gamma = gamma2 + gamma3;
+        ### Returns
+         * **Ok**: The moved coordinate, rounded to the metre.
+         * **Err**: `Errors::InvalidLatitude` if the moved point lies outside of the UTM
+           latitude range of 80°S..84°N.
 
-        // ---- scale: Karney 2011 Eq 25
+        # Examples
 
-        let sinphi = phi.sin();
-        let k2 = f64::sqrt(1.0 - e * e * sinphi * sinphi) * f64::sqrt(1.0 + tau * tau) / f64::sqrt(tau2 * tau2 + coslamda * coslamda);
-        let k3 = A / a * f64::sqrt(p2 * p2 + q2 * q2);
+        ```
+        let utm: Utm = LatLon::new(48.8582, 2.2945).unwrap().into();
+        let moved = utm.offset(350.0, 1200.0).unwrap(); // moved.to_string() = '31 N 448602 5413133'
+        ```
+        */
+
+        let moved = Utm {
+            easting: (self.easting as f64 + de).round() as i32,
+            northing: (self.northing as f64 + dn).round() as i32,
+            convergence: None,
+            scale: None,
+            ..*self
+        };
+
+        let ll = LatLon::from(moved);
+        if !(-80.0 <= ll.lat && ll.lat <= 84.0) { return Err(Errors::InvalidLatitude(ll.lat)); }
+        if ZonePolicy::Standard.zone_for(&ll)? == self.zone && Hemisphere::from(ll.lat) == self.hemisphere {
+            return Ok(moved);
+        }
+        Ok(Utm::from_ll(&ll))
+    }
+
+    pub fn projection(&self) -> TransverseMercator {
+        /*!
+        Returns the Transverse Mercator projection this coordinate's zone and hemisphere are
+        defined by.
+        */
 
-        let k = k0 * k2 * k3;
+        utm_projection(self.zone, self.hemisphere, self.datum)
+    }
 
-        // ------------
+    fn from_ll_in_zone(ll: &LatLon, zone: u8) -> Self {
+        /*!
+        Converts latitude/longitude to a UTM coordinate in the given zone, regardless of whether
+        that zone is the one the point would normally fall within.
+        */
 
-        // shift x/y to false origins
-        x = x + false_easting;             // make x relative to false easting
-        if y < 0.0 { y = y + false_northing; } // make y in southern hemisphere relative to false northing
+        let hemisphere = Hemisphere::from(ll.lat);
+        let tm = utm_projection(zone, hemisphere, ll.datum);
+        let p = tm.forward(ll);
 
         // round to reasonable precision
-        let to_precision = |x: i32, y: u32| -> i32 {
-            let p = i32::pow(10, y);
-            (x * p) as i32 / p as i32
-        };
         let to_precisionf = |x: f64, y: f64| -> f64 {
             let p = f64::powf(10.0, y);
             f64::round(x * p) / p
         };
 
         Utm {
-            zone: zone,
-            hemisphere: Hemisphere::from(ll.lat),
-            easting: to_precision(x as i32 % 100000, 6), // nm precision,
-            northing: to_precision(y as i32 % 100000, 6),
+            zone,
+            hemisphere,
+            easting: p.easting.round() as i32,
+            northing: p.northing.round() as i32,
             datum: ll.datum,
-            convergence: Some(to_precisionf(gamma.to_degrees(), 9.0)),
-            scale: Some(to_precisionf(k, 12.0)),
+            convergence: Some(to_precisionf(p.convergence, 9.0)),
+            scale: Some(to_precisionf(p.scale, 12.0)),
         }
     }
 
-    fn from_mgrs(mgrs: Mgrs) -> Self {
+    pub fn from_mgrs(mgrs: &Mgrs) -> Self {
         /*!
         Converts MGRS grid reference to UTM coordinate.
 
@@ -223,23 +267,49 @@ impl Utm {
         ```
         */
 
+        Utm::from_mgrs_with_datum(mgrs, Datum::Wgs84)
+    }
+
+    pub fn from_mgrs_with_datum(mgrs: &Mgrs, datum: Datum) -> Self {
+        /*!
+        Converts MGRS grid reference on a given datum to UTM coordinate, reading its 100km square
+        letters in the datum's lettering scheme (see `Datum::lettering`).
+
+        ### Params
+         * **mgrs**: Grid reference, as read from a map on `datum`.
+         * **datum**: Datum the grid reference is based on.
+
+        ### Returns
+         * A `Utm` struct on `datum`
+
+        # Examples
+
+        ```
+        let mgrs: Mgrs = "18S UU 23445 06273".parse().unwrap(); // read from a NAD27 map
+        let utm = Utm::from_mgrs_with_datum(&mgrs, Datum::Nad27); // utm.to_string() = '18 N 323445 4306273'
+        ```
+        */
+
         // get easting specified by e100k
-        let e100k_num = mgrs.gsid_100k.col.as_meters_from_zone(mgrs.gzd.zone);
+        let e100k_num = mgrs.gsid_100k.col.as_meters_from_zone(mgrs.gzd.zone) as i32;
 
         // get northing specified by n100k
-        let n100k_num = mgrs.gsid_100k.row.as_meters_from_zone(mgrs.gzd.zone);
+        let n100k_num = mgrs.gsid_100k.row.as_meters_with_lettering(mgrs.gzd.zone, datum.lettering()) as i32;
 
         // get latitude of (bottom of) band
         let lat_band: f64 = mgrs.gzd.band.into();
 
         // 100km grid square row letters repeat every 2,000km north; add enough 2,000km blocks to get
         // into required band
-        let utm: Utm = LatLon::new(lat_band, 0.0).unwrap().into();
-        let n_band = utm.northing; // northing of bottom of band
+        // the bottom of the band is lowest along the central meridian in the north and at the zone's
+        // edges in the south, so extend it to include the whole of its bottom-most 100km squares
+        let utm: Utm = LatLon::new(lat_band, 3.0).unwrap().into();
+        let n_band = utm.northing / 100000 * 100000 - 100000; // northing of bottom of band
         let mut n2m = 0; // northing of 2,000km block
-        while (n2m + n100k_num + mgrs.northing) < n_band { n2m += 2000000; }
+        while (n2m + n100k_num + mgrs.northing as i32) < n_band { n2m += 2000000; }
 
-        Utm::new(mgrs.gzd.zone, mgrs.gzd.band, e100k_num + mgrs.easting, n2m + n100k_num + mgrs.northing)
+        let utm = Utm::new(mgrs.gzd.zone, mgrs.gzd.band, e100k_num + mgrs.easting as i32, n2m + n100k_num + mgrs.northing as i32);
+        Utm { datum, ..utm }
     }
 
     // pub fn from_ll(ll: &LatLon) -> Self {
@@ -332,36 +402,46 @@ impl Utm {
     //     }
     // }
 
-    // pub fn to_mgrs(self, accuracy: Option<Accuracy>) -> Mgrs {
-    //     /*!
-    //     Converts UTM coordinate to MGRS reference.
-    //
-    //      @returns {Mgrs}
-    //      @throws  {Error} Invalid coordinate
-    //
-    //      @example
-    //        var utmCoord = new Utm(31, 'N', 448251, 5411932);
-    //        var mgrsRef = utmCoord.toMgrs(); // mgrsRef.toString() = '31U DQ 48251 11932'
-    //     */
-    //
-    //     let e100k = ColLetter::from_zone_and_easting(self.zone, self.easting);
-    //
-    //     let n100k = RowLetter::from_zone_and_northing(self.zone, self.northing);
-    //
-    //     // truncate easting/northing to within 100km grid square and round to nm precision
-    //     // round to reasonable precision
-    //     let to_precision = |x: i32, y: u32| -> usize {
-    //         let p = i32::pow(10, y);
-    //         (((x % p) * p) / p) as usize
-    //     };
-    //
-    //     Mgrs {
-    //         gzd: Gzd { zone: self.zone, band: LatBand::from(self.as_ll_e().lat) },
-    //         gsid_100k: GridSquareId100k { col: e100k, row: n100k },
-    //         easting: to_precision(self.easting, 6),
-    //         northing: to_precision(self.northing, 6),
-    //     }
-    // }
+    pub fn to_mgrs(&self, accuracy: Accuracy) -> Mgrs {
+        /*!
+        Converts UTM coordinate to MGRS reference, lettering the 100km square in the scheme of
+        this coordinate's datum (see `Datum::lettering`).
+
+        The easting/northing are truncated onto the grid at `accuracy`, as the military standard
+        requires, so the reference holds the south-west corner of the cell its digits name.
+
+        ### Params
+         * **accuracy**: Precision the resulting grid reference is reported at.
+
+        ### Returns
+         * The `Mgrs` grid reference for this coordinate.
+
+        # Examples
+
+        ```
+        let utm = Utm::new(31, 'N', 448251, 5411932);
+        let mgrs = utm.to_mgrs(Accuracy::One); // mgrs.to_string() = '31U DQ 48251 11932'
+        ```
+
+        # Panics
+
+        If the easting lies outside of the lettered 100km..900km range of the zone
+        */
+
+        let e100k = ColLetter::from_zone_and_easting(self.zone, self.easting);
+
+        let n100k = RowLetter::from_northing_with_lettering(self.zone, self.northing, self.datum.lettering());
+
+        // truncate easting/northing to within 100km grid square, then onto the grid at accuracy
+        let size = accuracy.as_distance();
+        Mgrs {
+            gzd: Gzd { zone: self.zone, band: LatBand::from(LatLon::from(*self).lat) },
+            gsid_100k: GridSquareId100k { col: e100k, row: n100k },
+            easting: (self.easting % 100000) as usize / size * size,
+            northing: (self.northing % 100000) as usize / size * size,
+            accuracy,
+        }
+    }
 
     // pub fn get_100k_id(&self) -> GridSquareId100k {
     //     /*!
@@ -400,14 +480,32 @@ impl Utm {
     }
 }
 
+fn utm_projection(zone: u8, hemisphere: Hemisphere, datum: Datum) -> TransverseMercator {
+    TransverseMercator {
+        lon0: (zone as i32 - 1) as f64 * 6.0 - 180.0 + 3.0, // longitude of central meridian
+        lat0: 0.0,
+        k0: 0.9996, // UTM scale on the central meridian
+        false_easting: FALSE_EASTING,
+        // make y in southern hemisphere relative to false northing
+        false_northing: if hemisphere == Hemisphere::S { FALSE_NORTHING } else { 0.0 },
+        ellipsoid: datum.ellipsoid(),
+    }
+}
+
 impl From<LatLon> for Utm {
     fn from(ll: LatLon) -> Self {
         Utm::from_ll(&ll)
     }
 }
 
+impl From<Mgrs> for Utm {
+    fn from(mgrs: Mgrs) -> Self {
+        Utm::from_mgrs(&mgrs)
+    }
+}
+
 impl fmt::Display for Utm {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", self.as_string(5))
+        write!(f, "{}", self.as_string(5))
     }
 }
@@ -0,0 +1,211 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use Errors;
+use latlon::LatLon;
+use datum::Datum;
+
+// radius of the sphere Web Mercator projects from, the WGS84 major radius
+const RADIUS: f64 = 6378137.0;
+// latitude at which the projection is square, the limit of the tile pyramid
+const MAX_LATITUDE: f64 = 85.0511287798066;
+// deepest zoom level whose tile indices fit a u32
+const MAX_ZOOM: u8 = 31;
+
+/// A point projected onto Web Mercator (EPSG:3857)
+#[derive(Copy, Clone, Debug, Default)]
+pub struct WebMercator {
+    /// Metres east of the prime meridian
+    pub x: f64,
+    /// Metres north of the equator
+    pub y: f64,
+}
+
+impl WebMercator {
+    pub fn from_ll(ll: &LatLon) -> Self {
+        /*!
+        Projects latitude/longitude onto Web Mercator.
+
+        Latitudes are clamped to ±85.0511°, where the projection's square extent ends.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        let wm = WebMercator::from_ll(&ll); // wm.to_string() = '255422.57 6250835.06'
+        ```
+        */
+
+        let ll = ll.to_datum(Datum::Wgs84);
+        let phi = ll.lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+        WebMercator {
+            x: RADIUS * ll.lon.to_radians(),
+            y: RADIUS * f64::ln(f64::tan(PI / 4.0 + phi / 2.0)),
+        }
+    }
+
+    pub fn to_ll(&self) -> LatLon {
+        /*!
+        Converts this point back to WGS84 latitude/longitude.
+        */
+
+        LatLon {
+            lat: (2.0 * f64::atan(f64::exp(self.y / RADIUS)) - PI / 2.0).to_degrees(),
+            lon: (self.x / RADIUS).to_degrees(),
+            ..Default::default()
+        }
+    }
+}
+
+/// An XYZ ("slippy map") tile, numbered from the north-west corner of the map
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tile {
+    pub x: u32,
+    pub y: u32,
+    /// Zoom level, the map is `2^z` tiles across
+    pub z: u8,
+}
+
+impl Tile {
+    pub fn from_ll(ll: &LatLon, zoom: u8) -> Self {
+        /*!
+        Returns the tile containing a point at a zoom level.
+
+        # Panics
+
+        If `zoom` is greater than 31.
+
+        # Examples
+
+        ```
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        let tile = Tile::from_ll(&ll, 12); // tile.to_string() = '12/2074/1409'
+        ```
+        */
+
+        if zoom > MAX_ZOOM { panic!("Invalid zoom level {}", zoom); }
+
+        let n = f64::powi(2.0, zoom as i32);
+        let p = WebMercator::from_ll(ll);
+        let x = f64::floor((p.x / (RADIUS * PI) + 1.0) / 2.0 * n);
+        let y = f64::floor((1.0 - p.y / (RADIUS * PI)) / 2.0 * n);
+        Tile {
+            x: x.max(0.0).min(n - 1.0) as u32,
+            y: y.max(0.0).min(n - 1.0) as u32,
+            z: zoom,
+        }
+    }
+
+    pub fn to_ll_rect(&self) -> [LatLon; 2] {
+        /*!
+        Returns the extent of this tile.
+
+        ### Return
+         * An array of `LatLon` structs which represents bottom-left, and top-right values.
+        */
+
+        let n = f64::powi(2.0, self.z as i32);
+        let corner = |x: f64, y: f64| WebMercator {
+            x: (x / n * 2.0 - 1.0) * RADIUS * PI,
+            y: (1.0 - y / n * 2.0) * RADIUS * PI,
+        }.to_ll();
+        [corner(self.x as f64, self.y as f64 + 1.0), corner(self.x as f64 + 1.0, self.y as f64)]
+    }
+
+    pub fn quadkey(&self) -> String {
+        /*!
+        Returns the quadkey of this tile, one digit per zoom level.
+
+        # Examples
+
+        ```
+        let tile = Tile { x: 3, y: 5, z: 3 };
+        assert_eq!("213", &*tile.quadkey());
+        ```
+        */
+
+        (0..self.z).rev().map(|i| {
+            let mask = 1 << i;
+            let mut digit = b'0';
+            if self.x & mask != 0 { digit += 1; }
+            if self.y & mask != 0 { digit += 2; }
+            digit as char
+        }).collect()
+    }
+
+    pub fn from_quadkey(quadkey: &str) -> Result<Self, Errors> {
+        /*!
+        Returns the tile a quadkey refers to.
+
+        ### Returns
+         * **Ok**: The tile, at the zoom level of the quadkey's length.
+         * **Err**: `Errors::InvalidGridReference` if the quadkey has digits other than 0-3, or is
+           more than 31 digits long.
+        */
+
+        if quadkey.len() > MAX_ZOOM as usize {
+            return Err(Errors::InvalidGridReference(quadkey.to_owned()));
+        }
+        let mut tile = Tile { x: 0, y: 0, z: quadkey.len() as u8 };
+        for b in quadkey.bytes() {
+            if !(b'0'..=b'0' + 3).contains(&b) {
+                return Err(Errors::InvalidGridReference(quadkey.to_owned()));
+            }
+            let digit = (b - b'0') as u32;
+            tile.x = tile.x << 1 | digit & 1;
+            tile.y = tile.y << 1 | digit >> 1;
+        }
+        Ok(tile)
+    }
+}
+
+impl From<WebMercator> for LatLon {
+    fn from(wm: WebMercator) -> Self {
+        wm.to_ll()
+    }
+}
+
+impl fmt::Display for WebMercator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} {:.2}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.z, self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Tile, WebMercator};
+    use latlon::LatLon;
+
+    #[test]
+    fn forward_and_inverse() {
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        let wm = WebMercator::from_ll(&ll);
+        let back = wm.to_ll();
+        assert!((back.lat - ll.lat).abs() < 1e-9);
+        assert!((back.lon - ll.lon).abs() < 1e-9);
+
+        let corner = WebMercator::from_ll(&LatLon { lat: 89.0, lon: 180.0, ..Default::default() });
+        assert!((corner.x - 20037508.342789244).abs() < 1e-6);
+        assert!((corner.y - 20037508.342789244).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tiles_and_quadkeys() {
+        let tile = Tile { x: 3, y: 5, z: 3 };
+        assert_eq!(&*tile.quadkey(), "213");
+        assert_eq!(Tile::from_quadkey("213").unwrap(), tile);
+        assert!(Tile::from_quadkey("214").is_err());
+
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        let tile = Tile::from_ll(&ll, 12);
+        let rect = tile.to_ll_rect();
+        assert!(rect[0].lat <= ll.lat && ll.lat < rect[1].lat);
+        assert!(rect[0].lon <= ll.lon && ll.lon < rect[1].lon);
+    }
+}
@@ -0,0 +1,165 @@
+use Errors;
+use Lon;
+use latlon::LatLon;
+
+/// How the UTM zone is chosen when converting from latitude/longitude
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum ZonePolicy {
+    /// Standard 6° zones, including the Norway (32V) and Svalbard (31X..37X) exceptions
+    #[default]
+    Standard,
+    /// Standard 6° zones, ignoring the Norway and Svalbard exceptions
+    IgnoreExceptions,
+    /// Always use the given zone (1..60 inclusive), regardless of where the point lies
+    Force(u8),
+    /// Use the zone whose central meridian is nearest the given longitude, such as the centroid
+    /// of a dataset, so every point in the dataset shares one zone
+    NearestCentralMeridian(Lon),
+}
+
+impl ZonePolicy {
+    pub fn zone_for(&self, ll: &LatLon) -> Result<u8, Errors> {
+        /*!
+        Picks the UTM zone a point is projected into under this policy.
+
+        ### Params
+         * **ll**: The point being converted.
+
+        ### Returns
+         * **Ok**: The UTM zone number (1..60 inclusive).
+         * **Err**: `Errors::InvalidZone` if a forced zone is not 1..60,
+           `Errors::InvalidLatitude` if a `Standard` point lies outside 80°S..84°N, or
+           `Errors::InvalidLongitude` if the longitude of `NearestCentralMeridian` is not finite.
+        */
+
+        match *self {
+            ZonePolicy::Standard => {
+                if !(-80.0 <= ll.lat && ll.lat <= 84.0) { return Err(Errors::InvalidLatitude(ll.lat)); }
+                Ok(standard_zone(ll))
+            },
+            ZonePolicy::IgnoreExceptions => Ok(regular_zone(ll.lon)),
+            ZonePolicy::Force(zone) => {
+                if !(1..=60).contains(&zone) { return Err(Errors::InvalidZone(zone)); }
+                Ok(zone)
+            },
+            // a central meridian sits in the middle of each regular zone, so the nearest one is
+            // that of the zone containing the longitude
+            ZonePolicy::NearestCentralMeridian(lon) => {
+                if !lon.is_finite() { return Err(Errors::InvalidLongitude(lon)); }
+                // bring the longitude into -180..180, keeping 180°E itself
+                Ok(regular_zone(180.0 - (180.0 - lon).rem_euclid(360.0)))
+            },
+        }
+    }
+}
+
+fn regular_zone(lon: Lon) -> u8 {
+    let zone = (f64::floor((lon + 180.0) / 6.0) + 1.0) as u8; // longitudinal zone
+    // 180°E belongs to zone 60
+    if zone > 60 { 60 } else { zone }
+}
+
+fn standard_zone(ll: &LatLon) -> u8 {
+    let mut zone = regular_zone(ll.lon);
+
+    // ---- handle Norway/Svalbard exceptions
+    // grid zones are 8° tall; 0°N is offset 10 into latitude bands array
+    let mgrs_lat_bands = b"CDEFGHJKLMNPQRSTUVWXX"; // X is repeated for 80-84°N
+    let lat_band = mgrs_lat_bands[f64::floor(ll.lat / 8.0 + 10.0) as usize];
+
+    // adjust zone for Norway
+    if zone == 31 && lat_band == b'V' && ll.lon >= 3.0 { zone += 1; }
+    // adjust zone for Svalbard
+    if zone == 32 && lat_band == b'X' && ll.lon <  9.0  { zone -= 1; }
+    else if zone == 32 && lat_band == b'X' && ll.lon >= 9.0  { zone += 1; }
+    else if zone == 34 && lat_band == b'X' && ll.lon <  21.0 { zone -= 1; }
+    else if zone == 34 && lat_band == b'X' && ll.lon >= 21.0 { zone += 1; }
+    else if zone == 36 && lat_band == b'X' && ll.lon <  33.0 { zone -= 1; }
+    else if zone == 36 && lat_band == b'X' && ll.lon >= 33.0 { zone += 1; }
+
+    zone
+}
+
+#[cfg(test)]
+mod test {
+    use super::ZonePolicy;
+    use latlon::LatLon;
+    use utm::Utm;
+    use Errors;
+
+    #[test]
+    fn standard() {
+        let zone = |lat, lon| ZonePolicy::Standard.zone_for(&LatLon::new(lat, lon).unwrap()).unwrap();
+        assert_eq!(zone(48.8582, 2.2945), 31);
+        assert_eq!(zone(-33.8568, 151.2153), 56);
+        assert_eq!(zone(0.0, 180.0), 60);
+        // 32V is widened west over Norway
+        assert_eq!(zone(60.0, 4.0), 32);
+        assert_eq!(zone(60.0, 2.0), 31);
+        // 31X, 33X, 35X and 37X are widened over Svalbard
+        assert_eq!(zone(79.0, 8.0), 31);
+        assert_eq!(zone(79.0, 10.0), 33);
+        assert_eq!(zone(79.0, 20.0), 33);
+        assert_eq!(zone(79.0, 22.0), 35);
+        assert_eq!(zone(79.0, 32.0), 35);
+        assert_eq!(zone(79.0, 34.0), 37);
+    }
+
+    #[test]
+    fn ignore_exceptions() {
+        let zone = |lat, lon| ZonePolicy::IgnoreExceptions.zone_for(&LatLon::new(lat, lon).unwrap()).unwrap();
+        assert_eq!(zone(60.0, 4.0), 31);
+        assert_eq!(zone(79.0, 10.0), 32);
+        assert_eq!(zone(48.8582, 2.2945), 31);
+    }
+
+    #[test]
+    fn force() {
+        let ll = LatLon::new(48.8582, 2.2945).unwrap();
+        assert_eq!(ZonePolicy::Force(30).zone_for(&ll).unwrap(), 30);
+        // forcing is allowed anywhere, but a zone far from the point cannot be projected into
+        assert_eq!(ZonePolicy::Force(45).zone_for(&ll).unwrap(), 45);
+        match Utm::from_ll_with_policy(&ll, ZonePolicy::Force(45)) {
+            Err(Errors::ZoneOutOfRange(45)) => {},
+            r => panic!("expected ZoneOutOfRange, got {:?}", r),
+        }
+        let utm = Utm::from_ll_with_policy(&ll, ZonePolicy::Force(30)).unwrap();
+        assert_eq!(utm.zone, 30);
+        assert!(utm.easting > 800000);
+
+        for &zone in &[0, 61] {
+            match ZonePolicy::Force(zone).zone_for(&ll) {
+                Err(Errors::InvalidZone(z)) => assert_eq!(z, zone),
+                r => panic!("expected InvalidZone, got {:?}", r),
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_central_meridian() {
+        // every point of a dataset centered on 4.5°E shares zone 31, even those in 32V
+        let policy = ZonePolicy::NearestCentralMeridian(4.5);
+        assert_eq!(policy.zone_for(&LatLon::new(60.0, 7.0).unwrap()).unwrap(), 31);
+        assert_eq!(policy.zone_for(&LatLon::new(-10.0, -1.0).unwrap()).unwrap(), 31);
+        assert_eq!(ZonePolicy::NearestCentralMeridian(-177.0).zone_for(&LatLon::default()).unwrap(), 1);
+        assert_eq!(ZonePolicy::NearestCentralMeridian(180.0).zone_for(&LatLon::default()).unwrap(), 60);
+        // longitudes outside -180..180 wrap around
+        assert_eq!(ZonePolicy::NearestCentralMeridian(200.0).zone_for(&LatLon::default()).unwrap(), 4);
+        assert_eq!(ZonePolicy::NearestCentralMeridian(-200.0).zone_for(&LatLon::default()).unwrap(), 57);
+        match ZonePolicy::NearestCentralMeridian(f64::NAN).zone_for(&LatLon::default()) {
+            Err(Errors::InvalidLongitude(_)) => {},
+            r => panic!("expected InvalidLongitude, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn outside_utm_limits() {
+        let ll = LatLon { lat: 88.0, lon: 10.0, ..Default::default() };
+        for &policy in &[ZonePolicy::Standard, ZonePolicy::Force(32), ZonePolicy::NearestCentralMeridian(10.0)] {
+            match Utm::from_ll_with_policy(&ll, policy) {
+                Err(Errors::InvalidLatitude(lat)) => assert_eq!(lat, 88.0),
+                r => panic!("expected InvalidLatitude, got {:?}", r),
+            }
+        }
+    }
+}
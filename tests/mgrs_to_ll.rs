@@ -1,19 +1,23 @@
-extern crate mgrs;
+extern crate coord;
+
+use coord::{LatLon, Mgrs, Utm};
 
 #[test]
 fn mgrs_to_ll_point() {
-    let ll = LatLon::from_mgrs("33UXP04");
-
-    assert_eq!(ll.lat, 48.2053484);
-    assert_eq!(ll.lon, 16.3459270);
+    let mgrs: Mgrs = "33UXP04".parse().unwrap();
+    let ll = mgrs.to_ll();
 
-    let ll = LatLon::rect_from_mgrs("33UXP04");
+    // the south-west corner of the 10km square
+    assert!((ll.lat - 48.2053484).abs() < 1e-6);
+    assert!((ll.lon - 16.3459270).abs() < 1e-6);
 
-    assert_eq!(ll[0].lon, 16.41450); // May need to change to close to
-    assert_eq!(ll[0].lat, 0.000001);
-    assert_eq!(ll[1].lon, 48.24949); // May need to change to close to
-    assert_eq!(ll[1].lat, 0.000001);
+    let utm = Utm::from(mgrs);
+    let ne = LatLon::from(Utm { easting: utm.easting + 10000, northing: utm.northing + 10000, ..utm });
+    let rect = [ll, ne];
 
+    // the center of the square is mgrs.js' point for the same reference
+    assert!(((rect[0].lon + rect[1].lon) / 2.0 - 16.41450).abs() < 1e-3);
+    assert!(((rect[0].lat + rect[1].lat) / 2.0 - 48.24949).abs() < 1e-3);
 
     // it('MGRS reference with highest accuracy correct.', function() {
     // mgrs.forward(point).should.equal("33UXP0500444998");
@@ -23,17 +27,17 @@ fn mgrs_to_ll_point() {
 
 #[test]
 fn mgrs_to_ll_point_near_zone_border() {
-  // var mgrsStr = "24XWT783908"; // near UTM zone border, so there are two ways to reference this
-  // var point = mgrs.toPoint(mgrsStr);
-  // it('Longitude of point from MGRS correct.', function() {
-  //   point[0].should.be.closeTo(-32.66433, 0.00001);
-  // });
-  // it('Latitude of point from MGRS correct.', function() {
-  //   point[1].should.be.closeTo(83.62778, 0.00001);
-  // });
-  // it('MGRS reference with 1-digit accuracy correct.', function() {
-  //   mgrs.forward(point,3).should.equal('25XEN041865');
-  // });
+  // near UTM zone border, so there are two ways to reference this
+  let mgrs: Mgrs = "24XWT783908".parse().unwrap();
+  // mgrs.js gives the center of the 100m square
+  let ll = mgrs.center();
+  assert!((ll.lon - -32.66433).abs() < 1e-5);
+  assert!((ll.lat - 83.62778).abs() < 1e-5);
+
+  let mgrs = mgrs.to_zone(25).unwrap();
+  assert_eq!(&*mgrs.to_string(), "25X EN 041 865");
+  assert_eq!(&*mgrs.to_zone(24).unwrap().to_string(), "24X WT 783 908");
+
   // it('MGRS reference with 5-digit accuracy, northing all zeros', function(){
   //   mgrs.forward([0,0],5).should.equal('31NAA6602100000');
   // });
@@ -41,3 +45,20 @@ fn mgrs_to_ll_point_near_zone_border() {
   //   mgrs.forward([0,0.00001],5).should.equal('31NAA6602100001');
   // });
 }
+
+#[test]
+fn utm_to_adjacent_zone() {
+  let utm = Utm::from(LatLon::new(48.8582, 2.2945).unwrap());
+  assert_eq!(utm.zone, 31);
+
+  let west = utm.to_zone(30).unwrap();
+  assert_eq!(west.zone, 30);
+  assert!(west.easting > 800000);
+
+  let back = LatLon::from(west);
+  assert!((back.lat - 48.8582).abs() < 1e-5);
+  assert!((back.lon - 2.2945).abs() < 1e-5);
+
+  assert!(utm.to_zone(61).is_err());
+  assert!(utm.to_zone(1).is_err());
+}